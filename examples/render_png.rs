@@ -0,0 +1,25 @@
+use epcgen::png::PngOptions;
+use epcgen::{CharacterSet, Epc, Identification, Version};
+
+fn main() {
+    let epc = Epc::builder()
+        .version(Version::V1)
+        .character_set(CharacterSet::UTF8)
+        .identification(Identification::Sct)
+        .bic("GENODEF1SLR")
+        .beneficiary("Codeberg e.V.")
+        .iban("DE90 8306 5408 0004 1042 42")
+        .amount("10.00")
+        .remittance(epcgen::Remittance::Text("for the good cause".to_string()))
+        .build()
+        .expect("example should work");
+    // Render a code of 30 mm for printing at 600 dpi.
+    let options = PngOptions {
+        dpi: 600,
+        size_mm: Some(30.0),
+        ..PngOptions::default()
+    };
+    let png = epc.render_png(&options).unwrap();
+    // Save the image.
+    std::fs::write("./examples_render_png_qrcode.png", png).unwrap();
+}
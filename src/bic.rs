@@ -0,0 +1,115 @@
+//! BIC utilities
+
+/// Check the structural validity of a BIC (ISO 9362)
+///
+/// A BIC consists of a 4-letter institution code, an ISO 3166 country code, a 2-character
+/// location code and an optional 3-character branch code.
+pub fn is_valid(bic: &str) -> bool {
+    (bic.len() == 8 || bic.len() == 11)
+        && bic
+            .chars()
+            .all(|c| c.is_ascii_digit() || c.is_ascii_uppercase())
+        && bic[0..4].chars().all(|c| c.is_ascii_uppercase())
+        && COUNTRY_CODES.binary_search(&&bic[4..6]).is_ok()
+}
+
+/// Get the country code of a valid BIC
+pub fn country(bic: &str) -> Option<&str> {
+    if is_valid(bic) { bic.get(4..6) } else { None }
+}
+
+/// Check whether a bank in the country of a BIC may hold accounts with IBANs of the given
+/// country
+///
+/// Apart from the own country, this holds for territories without IBANs of their own, like
+/// Jersey with GB or Réunion with FR IBANs.
+pub fn accepts_iban_country(bic_country: &str, iban_country: &str) -> bool {
+    bic_country == iban_country || IBAN_TERRITORIES.contains(&(bic_country, iban_country))
+}
+
+/// Territories with their own ISO 3166 code, whose banks hold accounts with IBANs of another
+/// country
+const IBAN_TERRITORIES: [(&str, &str); 17] = [
+    ("AX", "FI"),
+    ("BL", "FR"),
+    ("GF", "FR"),
+    ("GG", "GB"),
+    ("GP", "FR"),
+    ("IM", "GB"),
+    ("JE", "GB"),
+    ("MC", "FR"),
+    ("MF", "FR"),
+    ("MQ", "FR"),
+    ("NC", "FR"),
+    ("PF", "FR"),
+    ("PM", "FR"),
+    ("RE", "FR"),
+    ("TF", "FR"),
+    ("WF", "FR"),
+    ("YT", "FR"),
+];
+
+/// The ISO 3166-1 alpha-2 country codes plus XK (Kosovo), which is in use by SWIFT
+const COUNTRY_CODES: [&str; 250] = [
+    "AD", "AE", "AF", "AG", "AI", "AL", "AM", "AO", "AQ", "AR", "AS", "AT", "AU", "AW", "AX", "AZ",
+    "BA", "BB", "BD", "BE", "BF", "BG", "BH", "BI", "BJ", "BL", "BM", "BN", "BO", "BQ", "BR", "BS",
+    "BT", "BV", "BW", "BY", "BZ", "CA", "CC", "CD", "CF", "CG", "CH", "CI", "CK", "CL", "CM", "CN",
+    "CO", "CR", "CU", "CV", "CW", "CX", "CY", "CZ", "DE", "DJ", "DK", "DM", "DO", "DZ", "EC", "EE",
+    "EG", "EH", "ER", "ES", "ET", "FI", "FJ", "FK", "FM", "FO", "FR", "GA", "GB", "GD", "GE", "GF",
+    "GG", "GH", "GI", "GL", "GM", "GN", "GP", "GQ", "GR", "GS", "GT", "GU", "GW", "GY", "HK", "HM",
+    "HN", "HR", "HT", "HU", "ID", "IE", "IL", "IM", "IN", "IO", "IQ", "IR", "IS", "IT", "JE", "JM",
+    "JO", "JP", "KE", "KG", "KH", "KI", "KM", "KN", "KP", "KR", "KW", "KY", "KZ", "LA", "LB", "LC",
+    "LI", "LK", "LR", "LS", "LT", "LU", "LV", "LY", "MA", "MC", "MD", "ME", "MF", "MG", "MH", "MK",
+    "ML", "MM", "MN", "MO", "MP", "MQ", "MR", "MS", "MT", "MU", "MV", "MW", "MX", "MY", "MZ", "NA",
+    "NC", "NE", "NF", "NG", "NI", "NL", "NO", "NP", "NR", "NU", "NZ", "OM", "PA", "PE", "PF", "PG",
+    "PH", "PK", "PL", "PM", "PN", "PR", "PS", "PT", "PW", "PY", "QA", "RE", "RO", "RS", "RU", "RW",
+    "SA", "SB", "SC", "SD", "SE", "SG", "SH", "SI", "SJ", "SK", "SL", "SM", "SN", "SO", "SR", "SS",
+    "ST", "SV", "SX", "SY", "SZ", "TC", "TD", "TF", "TG", "TH", "TJ", "TK", "TL", "TM", "TN", "TO",
+    "TR", "TT", "TV", "TW", "TZ", "UA", "UG", "UM", "US", "UY", "UZ", "VA", "VC", "VE", "VG", "VI",
+    "VN", "VU", "WF", "WS", "XK", "YE", "YT", "ZA", "ZM", "ZW",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_bics_should_validate() {
+        assert!(is_valid("GENODEF1SLR"));
+        assert!(is_valid("DEUTDEFF"));
+        assert!(is_valid("BNPAFRPP"));
+        assert!(is_valid("RZOOAT2L303"));
+    }
+
+    #[test]
+    fn invalid_bics_should_fail() {
+        assert!(!is_valid(""));
+        assert!(!is_valid("HELLO"));
+        assert!(!is_valid("GENODEF1SL"));
+        assert!(!is_valid("GEN0DEF1SLR"));
+        assert!(!is_valid("GENOXXF1SLR"));
+        assert!(!is_valid("GENODEF1sLR"));
+        assert!(!is_valid("GENODEF1SL!"));
+        assert!(!is_valid("GENODEÜ1SL"));
+    }
+
+    #[test]
+    fn country_works() {
+        assert_eq!(country("GENODEF1SLR"), Some("DE"));
+        assert_eq!(country("HELLO"), None);
+    }
+
+    #[test]
+    fn accepts_iban_country_works() {
+        assert!(accepts_iban_country("DE", "DE"));
+        assert!(accepts_iban_country("JE", "GB"));
+        assert!(accepts_iban_country("RE", "FR"));
+        assert!(!accepts_iban_country("FR", "DE"));
+        assert!(!accepts_iban_country("GB", "JE"));
+    }
+
+    #[test]
+    fn country_codes_are_sorted() {
+        assert!(COUNTRY_CODES.is_sorted());
+    }
+}
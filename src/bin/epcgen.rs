@@ -0,0 +1,365 @@
+//! Command line interface to create EPC QR codes
+//!
+//! The payload is printed unless an output file is given, whose extension selects PNG or SVG.
+//! Errors exit with the codes documented in the README, so that scripts can react to them.
+
+use clap::Parser;
+use epcgen::png::{PngError, PngOptions};
+use epcgen::qr::{MIN_MODULE_SIZE_MM, QUIET_ZONE, QrCode};
+use epcgen::svg::{SvgError, SvgOptions};
+use epcgen::{Builder, EpcError, Identification, Purpose, Remittance};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+/// Create EPC QR codes for SEPA credit transfers
+#[derive(Debug, Parser)]
+#[command(name = "epcgen", disable_version_flag = true)]
+struct Args {
+    /// Version of the EPC, 001 or 002
+    #[arg(long, default_value = "002")]
+    version: String,
+    /// Character set, 1 (UTF-8) to 8 (ISO 8859-15)
+    #[arg(long, default_value = "1")]
+    character_set: String,
+    /// Use an instant credit transfer
+    #[arg(long)]
+    inst: bool,
+    /// BIC of the bank of the beneficiary
+    #[arg(long)]
+    bic: Option<String>,
+    /// Name of the beneficiary
+    #[arg(long)]
+    beneficiary: Option<String>,
+    /// IBAN of the account of the beneficiary
+    #[arg(long)]
+    iban: Option<String>,
+    /// Amount in euros, e.g. 12.34
+    #[arg(long)]
+    amount: Option<String>,
+    /// Purpose code, e.g. CHAR
+    #[arg(long)]
+    purpose: Option<String>,
+    /// Structured RF creditor reference
+    #[arg(long, conflicts_with = "text")]
+    reference: Option<String>,
+    /// Unstructured remittance text
+    #[arg(long)]
+    text: Option<String>,
+    /// Information for the originator
+    #[arg(long)]
+    information: Option<String>,
+    /// Write the QR code to a .png or .svg file instead of printing the payload
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+    /// Width of the written QR code in millimetres
+    #[arg(long)]
+    size_mm: Option<f64>,
+}
+
+/// Failure of a run, mapped onto the exit code
+#[derive(Debug)]
+enum Failure {
+    Epc(EpcError),
+    Size { size_mm: f64, min_mm: f64 },
+    Png(PngError),
+    Svg(SvgError),
+    UnsupportedOutput,
+    Io(std::io::Error),
+}
+
+impl From<EpcError> for Failure {
+    fn from(e: EpcError) -> Self {
+        Failure::Epc(e)
+    }
+}
+
+impl From<PngError> for Failure {
+    fn from(e: PngError) -> Self {
+        match e {
+            PngError::Epc(e) => Failure::Epc(e),
+            e => Failure::Png(e),
+        }
+    }
+}
+
+impl From<SvgError> for Failure {
+    fn from(e: SvgError) -> Self {
+        match e {
+            SvgError::Epc(e) => Failure::Epc(e),
+            e => Failure::Svg(e),
+        }
+    }
+}
+
+impl From<std::io::Error> for Failure {
+    fn from(e: std::io::Error) -> Self {
+        Failure::Io(e)
+    }
+}
+
+impl Failure {
+    fn exit_code(&self) -> u8 {
+        match self {
+            Failure::Io(_) => 1,
+            // Usage errors share the code clap uses
+            Failure::UnsupportedOutput => 2,
+            Failure::Size { .. } | Failure::Png(_) | Failure::Svg(_) => 3,
+            Failure::Epc(e) => epc_exit_code(e),
+        }
+    }
+}
+
+impl std::fmt::Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Epc(e) => write!(f, "{}", e),
+            Failure::Size { size_mm, min_mm } => write!(
+                f,
+                "Invalid size {} mm, the code needs at least {:.1} mm to be scanned",
+                size_mm, min_mm
+            ),
+            Failure::Png(e) => write!(f, "{}", e),
+            Failure::Svg(e) => write!(f, "{}", e),
+            Failure::UnsupportedOutput => write!(f, "Output file must end with .png or .svg"),
+            Failure::Io(e) => write!(f, "Writing the output failed: {}", e),
+        }
+    }
+}
+
+/// Exit code of each EPC error, starting at 10 in the order of the variants, with the deprecated
+/// variant at the end
+fn epc_exit_code(e: &EpcError) -> u8 {
+    match e {
+        EpcError::MissingVersion => 10,
+        EpcError::MissingCharacterSet => 11,
+        EpcError::MissingIdentification => 12,
+        EpcError::BICRequiredInConfiguredVersion => 13,
+        EpcError::MissingBeneficiary => 14,
+        EpcError::InvalidIBAN => 15,
+        EpcError::MissingIBAN => 16,
+        EpcError::InvalidAmount => 17,
+        EpcError::InvalidPurpose => 18,
+        EpcError::InvalidRemittanceReference => 19,
+        EpcError::FieldTooLong { .. } => 20,
+        EpcError::InvalidBICLength { .. } => 21,
+        EpcError::InvalidBIC => 22,
+        EpcError::PayloadTooLong { .. } => 23,
+        EpcError::ControlCharacter { .. } => 24,
+        EpcError::MalformedPayload => 25,
+        EpcError::InvalidEncoding => 26,
+        EpcError::InvalidServiceTag => 27,
+        EpcError::InvalidVersion => 28,
+        EpcError::InvalidCharacterSet => 29,
+        EpcError::InvalidIdentification => 30,
+        EpcError::UnencodableCharacter { .. } => 31,
+        #[allow(deprecated)]
+        EpcError::RemittanceTextTooLong => 32,
+    }
+}
+
+/// Map the arguments onto a builder
+fn builder(args: &Args) -> Result<Builder<'_>, EpcError> {
+    let mut builder = Builder::new()
+        .version(args.version.parse()?)
+        .character_set(args.character_set.parse()?)
+        .identification(if args.inst {
+            Identification::Inst
+        } else {
+            Identification::Sct
+        });
+    if let Some(bic) = &args.bic {
+        builder = builder.bic(bic.as_str());
+    }
+    if let Some(beneficiary) = &args.beneficiary {
+        builder = builder.beneficiary(beneficiary.as_str());
+    }
+    if let Some(iban) = &args.iban {
+        builder = builder.iban(iban.as_str());
+    }
+    if let Some(amount) = &args.amount {
+        builder = builder.amount(amount.as_str());
+    }
+    if let Some(purpose) = &args.purpose {
+        builder = builder.purpose(purpose.parse::<Purpose>()?);
+    }
+    if let Some(reference) = &args.reference {
+        builder = builder.remittance(Remittance::Reference(reference.clone()));
+    }
+    if let Some(text) = &args.text {
+        builder = builder.remittance(Remittance::Text(text.clone()));
+    }
+    if let Some(information) = &args.information {
+        builder = builder.information(information.as_str());
+    }
+    Ok(builder)
+}
+
+/// Check the requested size once for all formats, which all keep the standard quiet zone
+fn check_size(code: &QrCode, size_mm: f64) -> Result<(), Failure> {
+    let min_mm = (code.size() + 2 * QUIET_ZONE) as f64 * MIN_MODULE_SIZE_MM;
+    if size_mm.is_finite() && size_mm >= min_mm {
+        Ok(())
+    } else {
+        Err(Failure::Size { size_mm, min_mm })
+    }
+}
+
+fn run(args: &Args) -> Result<(), Failure> {
+    let (epc, warnings) = builder(args)?.build_with_warnings()?;
+    for warning in warnings {
+        eprintln!("Warning: {}", warning);
+    }
+
+    let Some(output) = &args.output else {
+        println!("{}", epc);
+        return Ok(());
+    };
+    if let Some(size_mm) = args.size_mm {
+        check_size(&epc.to_qr_code()?, size_mm)?;
+    }
+    let extension = output
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase());
+    match extension.as_deref() {
+        Some("png") => {
+            let options = PngOptions {
+                size_mm: args.size_mm,
+                ..PngOptions::default()
+            };
+            std::fs::write(output, epc.render_png(&options)?)?;
+        }
+        Some("svg") => {
+            let options = SvgOptions {
+                size_mm: args.size_mm,
+                ..SvgOptions::default()
+            };
+            std::fs::write(output, epc.render_svg(&options)?)?;
+        }
+        _ => return Err(Failure::UnsupportedOutput),
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(failure) => {
+            eprintln!("Error: {}", failure);
+            ExitCode::from(failure.exit_code())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Args {
+        Args::try_parse_from(std::iter::once("epcgen").chain(args.iter().copied())).unwrap()
+    }
+
+    #[test]
+    fn mapping_arguments_works() {
+        let args = args(&[
+            "--version",
+            "001",
+            "--inst",
+            "--bic",
+            "GENODEF1SLR",
+            "--beneficiary",
+            "Codeberg e.V.",
+            "--iban",
+            "DE90 8306 5408 0004 1042 42",
+            "--amount",
+            "10.00",
+            "--purpose",
+            "CHAR",
+            "--reference",
+            "RF18539007547034",
+        ]);
+        let epc = builder(&args).unwrap().build().unwrap();
+        assert_eq!(
+            epc.to_string(),
+            "BCD\n001\n1\nINST\nGENODEF1SLR\nCodeberg e.V.\nDE90830654080004104242\nEUR10.00\nCHAR\nRF18539007547034\n\n"
+        );
+    }
+
+    #[test]
+    fn errors_map_to_exit_codes() {
+        let failure = |a: &[&str]| run(&args(a)).unwrap_err().exit_code();
+        assert_eq!(failure(&["--iban", "DE90830654080004104242"]), 14);
+        assert_eq!(
+            failure(&["--beneficiary", "Codeberg e.V.", "--iban", "DE00"]),
+            15
+        );
+        assert_eq!(failure(&["--version", "3"]), 28);
+        assert_eq!(
+            failure(&[
+                "--beneficiary",
+                "Codeberg e.V.",
+                "--iban",
+                "DE90830654080004104242",
+                "--output",
+                "code.gif"
+            ]),
+            2
+        );
+    }
+
+    /// A fresh temporary directory for the output of a test
+    fn temp_dir(test: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("epcgen_{}_{}", test, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write(output: &std::path::Path, size_mm: &str) -> Result<(), Failure> {
+        run(&args(&[
+            "--beneficiary",
+            "Codeberg e.V.",
+            "--iban",
+            "DE90830654080004104242",
+            "--output",
+            output.to_str().unwrap(),
+            &format!("--size-mm={}", size_mm),
+        ]))
+    }
+
+    #[test]
+    fn writing_files_works() {
+        let dir = temp_dir("writing_files_works");
+        let png = dir.join("code.png");
+        write(&png, "30").unwrap();
+        assert!(std::fs::read(&png).unwrap().starts_with(b"\x89PNG"));
+        let svg = dir.join("code.SVG");
+        write(&svg, "30").unwrap();
+        let svg = std::fs::read_to_string(&svg).unwrap();
+        assert!(svg.starts_with("<?xml"));
+        assert!(svg.contains(r#"width="30mm""#));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn unusable_sizes_should_fail() {
+        let dir = temp_dir("unusable_sizes_should_fail");
+        for file in ["code.png", "code.svg"] {
+            let output = dir.join(file);
+            for size_mm in ["5", "-5", "0", "NaN", "inf"] {
+                let failure = write(&output, size_mm).unwrap_err();
+                assert!(matches!(failure, Failure::Size { .. }));
+                assert_eq!(failure.exit_code(), 3);
+            }
+            assert!(!output.exists());
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn conflicting_remittances_should_fail() {
+        let r = Args::try_parse_from(["epcgen", "--reference", "RF18539007547034", "--text", "x"]);
+        assert!(r.is_err());
+    }
+}
@@ -0,0 +1,181 @@
+//! ISO-8859-1, -2, -4, -5, -7, -10 and -15 with the tables from unicode.org MAPPINGS/ISO8859
+
+use crate::CharacterSet;
+
+/// Encode a string in the given character set, fails with the first character that can't be
+/// represented
+pub(crate) fn encode(character_set: CharacterSet, s: &str) -> Result<Vec<u8>, char> {
+    let table = match upper_half(character_set) {
+        Some(table) => table,
+        None => return Ok(s.as_bytes().to_vec()),
+    };
+    s.chars()
+        .map(|c| match c as u32 {
+            v if v < 0xA0 => Ok(v as u8),
+            v => table
+                .iter()
+                .position(|&t| t != 0 && u32::from(t) == v)
+                .map(|i| 0xA0 + i as u8)
+                .ok_or(c),
+        })
+        .collect()
+}
+
+/// Decode bytes in the given character set, `None` if they aren't valid in it
+pub(crate) fn decode(character_set: CharacterSet, bytes: &[u8]) -> Option<String> {
+    let table = match upper_half(character_set) {
+        Some(table) => table,
+        None => return String::from_utf8(bytes.to_vec()).ok(),
+    };
+    bytes
+        .iter()
+        .map(|&b| match b {
+            b if b < 0xA0 => Some(char::from(b)),
+            b => match table[usize::from(b - 0xA0)] {
+                0 => None,
+                t => char::from_u32(u32::from(t)),
+            },
+        })
+        .collect()
+}
+
+fn upper_half(character_set: CharacterSet) -> Option<&'static [u16; 96]> {
+    match character_set {
+        CharacterSet::UTF8 => None,
+        CharacterSet::Iso8859_1 => Some(&ISO_8859_1),
+        CharacterSet::Iso8859_2 => Some(&ISO_8859_2),
+        CharacterSet::Iso8859_4 => Some(&ISO_8859_4),
+        CharacterSet::Iso8859_5 => Some(&ISO_8859_5),
+        CharacterSet::Iso8859_7 => Some(&ISO_8859_7),
+        CharacterSet::Iso8859_10 => Some(&ISO_8859_10),
+        CharacterSet::Iso8859_15 => Some(&ISO_8859_15),
+    }
+}
+
+/// Upper half (0xA0-0xFF) of ISO-8859-1, identical to the Unicode code points
+const ISO_8859_1: [u16; 96] = {
+    let mut table = [0; 96];
+    let mut i = 0;
+    while i < 96 {
+        table[i] = 0xA0 + i as u16;
+        i += 1;
+    }
+    table
+};
+
+/// Upper half (0xA0-0xFF) of ISO-8859-2, 0 marks unassigned bytes
+const ISO_8859_2: [u16; 96] = [
+    0x00A0, 0x0104, 0x02D8, 0x0141, 0x00A4, 0x013D, 0x015A, 0x00A7, 0x00A8, 0x0160, 0x015E, 0x0164,
+    0x0179, 0x00AD, 0x017D, 0x017B, 0x00B0, 0x0105, 0x02DB, 0x0142, 0x00B4, 0x013E, 0x015B, 0x02C7,
+    0x00B8, 0x0161, 0x015F, 0x0165, 0x017A, 0x02DD, 0x017E, 0x017C, 0x0154, 0x00C1, 0x00C2, 0x0102,
+    0x00C4, 0x0139, 0x0106, 0x00C7, 0x010C, 0x00C9, 0x0118, 0x00CB, 0x011A, 0x00CD, 0x00CE, 0x010E,
+    0x0110, 0x0143, 0x0147, 0x00D3, 0x00D4, 0x0150, 0x00D6, 0x00D7, 0x0158, 0x016E, 0x00DA, 0x0170,
+    0x00DC, 0x00DD, 0x0162, 0x00DF, 0x0155, 0x00E1, 0x00E2, 0x0103, 0x00E4, 0x013A, 0x0107, 0x00E7,
+    0x010D, 0x00E9, 0x0119, 0x00EB, 0x011B, 0x00ED, 0x00EE, 0x010F, 0x0111, 0x0144, 0x0148, 0x00F3,
+    0x00F4, 0x0151, 0x00F6, 0x00F7, 0x0159, 0x016F, 0x00FA, 0x0171, 0x00FC, 0x00FD, 0x0163, 0x02D9,
+];
+
+/// Upper half (0xA0-0xFF) of ISO-8859-4, 0 marks unassigned bytes
+const ISO_8859_4: [u16; 96] = [
+    0x00A0, 0x0104, 0x0138, 0x0156, 0x00A4, 0x0128, 0x013B, 0x00A7, 0x00A8, 0x0160, 0x0112, 0x0122,
+    0x0166, 0x00AD, 0x017D, 0x00AF, 0x00B0, 0x0105, 0x02DB, 0x0157, 0x00B4, 0x0129, 0x013C, 0x02C7,
+    0x00B8, 0x0161, 0x0113, 0x0123, 0x0167, 0x014A, 0x017E, 0x014B, 0x0100, 0x00C1, 0x00C2, 0x00C3,
+    0x00C4, 0x00C5, 0x00C6, 0x012E, 0x010C, 0x00C9, 0x0118, 0x00CB, 0x0116, 0x00CD, 0x00CE, 0x012A,
+    0x0110, 0x0145, 0x014C, 0x0136, 0x00D4, 0x00D5, 0x00D6, 0x00D7, 0x00D8, 0x0172, 0x00DA, 0x00DB,
+    0x00DC, 0x0168, 0x016A, 0x00DF, 0x0101, 0x00E1, 0x00E2, 0x00E3, 0x00E4, 0x00E5, 0x00E6, 0x012F,
+    0x010D, 0x00E9, 0x0119, 0x00EB, 0x0117, 0x00ED, 0x00EE, 0x012B, 0x0111, 0x0146, 0x014D, 0x0137,
+    0x00F4, 0x00F5, 0x00F6, 0x00F7, 0x00F8, 0x0173, 0x00FA, 0x00FB, 0x00FC, 0x0169, 0x016B, 0x02D9,
+];
+
+/// Upper half (0xA0-0xFF) of ISO-8859-5, 0 marks unassigned bytes
+const ISO_8859_5: [u16; 96] = [
+    0x00A0, 0x0401, 0x0402, 0x0403, 0x0404, 0x0405, 0x0406, 0x0407, 0x0408, 0x0409, 0x040A, 0x040B,
+    0x040C, 0x00AD, 0x040E, 0x040F, 0x0410, 0x0411, 0x0412, 0x0413, 0x0414, 0x0415, 0x0416, 0x0417,
+    0x0418, 0x0419, 0x041A, 0x041B, 0x041C, 0x041D, 0x041E, 0x041F, 0x0420, 0x0421, 0x0422, 0x0423,
+    0x0424, 0x0425, 0x0426, 0x0427, 0x0428, 0x0429, 0x042A, 0x042B, 0x042C, 0x042D, 0x042E, 0x042F,
+    0x0430, 0x0431, 0x0432, 0x0433, 0x0434, 0x0435, 0x0436, 0x0437, 0x0438, 0x0439, 0x043A, 0x043B,
+    0x043C, 0x043D, 0x043E, 0x043F, 0x0440, 0x0441, 0x0442, 0x0443, 0x0444, 0x0445, 0x0446, 0x0447,
+    0x0448, 0x0449, 0x044A, 0x044B, 0x044C, 0x044D, 0x044E, 0x044F, 0x2116, 0x0451, 0x0452, 0x0453,
+    0x0454, 0x0455, 0x0456, 0x0457, 0x0458, 0x0459, 0x045A, 0x045B, 0x045C, 0x00A7, 0x045E, 0x045F,
+];
+
+/// Upper half (0xA0-0xFF) of ISO-8859-7, 0 marks unassigned bytes
+const ISO_8859_7: [u16; 96] = [
+    0x00A0, 0x2018, 0x2019, 0x00A3, 0x20AC, 0x20AF, 0x00A6, 0x00A7, 0x00A8, 0x00A9, 0x037A, 0x00AB,
+    0x00AC, 0x00AD, 0x0000, 0x2015, 0x00B0, 0x00B1, 0x00B2, 0x00B3, 0x0384, 0x0385, 0x0386, 0x00B7,
+    0x0388, 0x0389, 0x038A, 0x00BB, 0x038C, 0x00BD, 0x038E, 0x038F, 0x0390, 0x0391, 0x0392, 0x0393,
+    0x0394, 0x0395, 0x0396, 0x0397, 0x0398, 0x0399, 0x039A, 0x039B, 0x039C, 0x039D, 0x039E, 0x039F,
+    0x03A0, 0x03A1, 0x0000, 0x03A3, 0x03A4, 0x03A5, 0x03A6, 0x03A7, 0x03A8, 0x03A9, 0x03AA, 0x03AB,
+    0x03AC, 0x03AD, 0x03AE, 0x03AF, 0x03B0, 0x03B1, 0x03B2, 0x03B3, 0x03B4, 0x03B5, 0x03B6, 0x03B7,
+    0x03B8, 0x03B9, 0x03BA, 0x03BB, 0x03BC, 0x03BD, 0x03BE, 0x03BF, 0x03C0, 0x03C1, 0x03C2, 0x03C3,
+    0x03C4, 0x03C5, 0x03C6, 0x03C7, 0x03C8, 0x03C9, 0x03CA, 0x03CB, 0x03CC, 0x03CD, 0x03CE, 0x0000,
+];
+
+/// Upper half (0xA0-0xFF) of ISO-8859-10, 0 marks unassigned bytes
+const ISO_8859_10: [u16; 96] = [
+    0x00A0, 0x0104, 0x0112, 0x0122, 0x012A, 0x0128, 0x0136, 0x00A7, 0x013B, 0x0110, 0x0160, 0x0166,
+    0x017D, 0x00AD, 0x016A, 0x014A, 0x00B0, 0x0105, 0x0113, 0x0123, 0x012B, 0x0129, 0x0137, 0x00B7,
+    0x013C, 0x0111, 0x0161, 0x0167, 0x017E, 0x2015, 0x016B, 0x014B, 0x0100, 0x00C1, 0x00C2, 0x00C3,
+    0x00C4, 0x00C5, 0x00C6, 0x012E, 0x010C, 0x00C9, 0x0118, 0x00CB, 0x0116, 0x00CD, 0x00CE, 0x00CF,
+    0x00D0, 0x0145, 0x014C, 0x00D3, 0x00D4, 0x00D5, 0x00D6, 0x0168, 0x00D8, 0x0172, 0x00DA, 0x00DB,
+    0x00DC, 0x00DD, 0x00DE, 0x00DF, 0x0101, 0x00E1, 0x00E2, 0x00E3, 0x00E4, 0x00E5, 0x00E6, 0x012F,
+    0x010D, 0x00E9, 0x0119, 0x00EB, 0x0117, 0x00ED, 0x00EE, 0x00EF, 0x00F0, 0x0146, 0x014D, 0x00F3,
+    0x00F4, 0x00F5, 0x00F6, 0x0169, 0x00F8, 0x0173, 0x00FA, 0x00FB, 0x00FC, 0x00FD, 0x00FE, 0x0138,
+];
+
+/// Upper half (0xA0-0xFF) of ISO-8859-15, 0 marks unassigned bytes
+const ISO_8859_15: [u16; 96] = [
+    0x00A0, 0x00A1, 0x00A2, 0x00A3, 0x20AC, 0x00A5, 0x0160, 0x00A7, 0x0161, 0x00A9, 0x00AA, 0x00AB,
+    0x00AC, 0x00AD, 0x00AE, 0x00AF, 0x00B0, 0x00B1, 0x00B2, 0x00B3, 0x017D, 0x00B5, 0x00B6, 0x00B7,
+    0x017E, 0x00B9, 0x00BA, 0x00BB, 0x0152, 0x0153, 0x0178, 0x00BF, 0x00C0, 0x00C1, 0x00C2, 0x00C3,
+    0x00C4, 0x00C5, 0x00C6, 0x00C7, 0x00C8, 0x00C9, 0x00CA, 0x00CB, 0x00CC, 0x00CD, 0x00CE, 0x00CF,
+    0x00D0, 0x00D1, 0x00D2, 0x00D3, 0x00D4, 0x00D5, 0x00D6, 0x00D7, 0x00D8, 0x00D9, 0x00DA, 0x00DB,
+    0x00DC, 0x00DD, 0x00DE, 0x00DF, 0x00E0, 0x00E1, 0x00E2, 0x00E3, 0x00E4, 0x00E5, 0x00E6, 0x00E7,
+    0x00E8, 0x00E9, 0x00EA, 0x00EB, 0x00EC, 0x00ED, 0x00EE, 0x00EF, 0x00F0, 0x00F1, 0x00F2, 0x00F3,
+    0x00F4, 0x00F5, 0x00F6, 0x00F7, 0x00F8, 0x00F9, 0x00FA, 0x00FB, 0x00FC, 0x00FD, 0x00FE, 0x00FF,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encoding_works() {
+        assert_eq!(encode(CharacterSet::UTF8, "Müller"), Ok("Müller".into()));
+        assert_eq!(
+            encode(CharacterSet::Iso8859_1, "Müller"),
+            Ok(b"M\xfcller".to_vec())
+        );
+        assert_eq!(
+            encode(CharacterSet::Iso8859_15, "5€"),
+            Ok(b"5\xa4".to_vec())
+        );
+        assert_eq!(
+            encode(CharacterSet::Iso8859_2, "Łódź"),
+            Ok(b"\xa3\xf3d\xbc".to_vec())
+        );
+        assert_eq!(encode(CharacterSet::Iso8859_5, "Щ"), Ok(b"\xc9".to_vec()));
+        assert_eq!(encode(CharacterSet::Iso8859_7, "Ω"), Ok(b"\xd9".to_vec()));
+    }
+
+    #[test]
+    fn encoding_unrepresentable_characters_should_fail() {
+        assert_eq!(encode(CharacterSet::Iso8859_1, "5€"), Err('€'));
+        assert_eq!(encode(CharacterSet::Iso8859_5, "Müller"), Err('ü'));
+        assert_eq!(encode(CharacterSet::Iso8859_10, "Ω"), Err('Ω'));
+    }
+
+    #[test]
+    fn decoding_works() {
+        assert_eq!(
+            decode(CharacterSet::Iso8859_1, b"M\xfcller"),
+            Some("Müller".to_string())
+        );
+        assert_eq!(
+            decode(CharacterSet::Iso8859_4, b"\xa1\xbd"),
+            Some("ĄŊ".to_string())
+        );
+        assert_eq!(decode(CharacterSet::Iso8859_7, b"\xae"), None);
+        assert_eq!(decode(CharacterSet::UTF8, b"M\xfcller"), None);
+    }
+}
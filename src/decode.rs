@@ -0,0 +1,255 @@
+//! Reading EPC QR codes from PNG and JPEG images
+//!
+//! This allows checking that a rendered code scans back to the same payment, and reading the
+//! codes on received invoices. Images with several QR codes are searched for the first one
+//! carrying an EPC payload.
+//!
+//! # Examples
+//! ```no_run
+//! use epcgen::{CharacterSet, Epc, Identification, Version};
+//!
+//! let epc = Epc::builder()
+//!     .version(Version::V2)
+//!     .character_set(CharacterSet::UTF8)
+//!     .identification(Identification::Sct)
+//!     .beneficiary("Codeberg e.V.")
+//!     .iban("DE90 8306 5408 0004 1042 42")
+//!     .build()
+//!     .unwrap();
+//! epc.verify_image("qrcode.png").unwrap();
+//! ```
+
+use crate::{Epc, EpcError};
+use std::error::Error;
+use std::fmt::Display;
+use std::path::Path;
+
+/// Possible errors when reading an EPC from an image
+#[derive(Debug, PartialEq, Clone)]
+pub enum DecodeError {
+    /// The image can't be read
+    Image(String),
+    /// The image contains no QR code with an EPC payload
+    NoEpcCode,
+    /// The EPC payload in the image is invalid
+    Epc(EpcError),
+    /// The image contains another EPC than the expected one
+    Mismatch(Box<Epc>),
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecodeError::Image(e) => write!(f, "Image can't be read: {}", e),
+            DecodeError::NoEpcCode => write!(f, "No EPC QR code found in image"),
+            DecodeError::Epc(e) => write!(f, "{}", e),
+            DecodeError::Mismatch(_) => write!(f, "Image contains another EPC than expected"),
+        }
+    }
+}
+
+impl Error for DecodeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DecodeError::Epc(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<EpcError> for DecodeError {
+    fn from(e: EpcError) -> Self {
+        DecodeError::Epc(e)
+    }
+}
+
+impl Epc {
+    /// Read the first EPC QR code from a PNG or JPEG file
+    pub fn from_image<P: AsRef<Path>>(path: P) -> Result<Epc, DecodeError> {
+        let image = image::open(path).map_err(|e| DecodeError::Image(e.to_string()))?;
+        decode(image)
+    }
+
+    /// Read the first EPC QR code from the bytes of a PNG or JPEG image
+    pub fn from_image_bytes(bytes: &[u8]) -> Result<Epc, DecodeError> {
+        let image =
+            image::load_from_memory(bytes).map_err(|e| DecodeError::Image(e.to_string()))?;
+        decode(image)
+    }
+
+    /// Check that the PNG or JPEG file contains a QR code of exactly this EPC
+    pub fn verify_image<P: AsRef<Path>>(&self, path: P) -> Result<(), DecodeError> {
+        let decoded = Epc::from_image(path)?;
+        if decoded == *self {
+            Ok(())
+        } else {
+            Err(DecodeError::Mismatch(Box::new(decoded)))
+        }
+    }
+}
+
+fn decode(image: image::DynamicImage) -> Result<Epc, DecodeError> {
+    let mut prepared = rqrr::PreparedImage::prepare(image.to_luma8());
+    let mut error = None;
+    for grid in prepared.detect_grids() {
+        let mut payload = Vec::new();
+        // The ECI designators are skipped, the payload declares its character set itself
+        if grid.decode_to(&mut payload).is_err() {
+            continue;
+        }
+        if payload.starts_with(b"BCD\n") || payload.starts_with(b"BCD\r\n") {
+            match Epc::parse(&payload) {
+                Ok(epc) => return Ok(epc),
+                // Another code might still carry a valid EPC
+                Err(e) => {
+                    error.get_or_insert(e);
+                }
+            }
+        }
+    }
+    Err(error.map_or(DecodeError::NoEpcCode, DecodeError::Epc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CharacterSet, Identification, Remittance, Version, test_epc};
+    use image::{ImageFormat, Luma};
+    use std::io::Cursor;
+
+    fn image(payload: &[u8], format: ImageFormat) -> Vec<u8> {
+        images(&[payload], format)
+    }
+
+    /// An image with the codes of the payloads next to each other
+    fn images(payloads: &[&[u8]], format: ImageFormat) -> Vec<u8> {
+        let codes: Vec<_> = payloads
+            .iter()
+            .map(|p| qrcode::QrCode::new(p).unwrap().render::<Luma<u8>>().build())
+            .collect();
+        let width = codes.iter().map(|c| c.width()).sum();
+        let height = codes.iter().map(|c| c.height()).max().unwrap();
+        let mut combined = image::GrayImage::from_pixel(width, height, Luma([255]));
+        let mut x = 0;
+        for code in &codes {
+            image::imageops::replace(&mut combined, code, x, 0);
+            x += i64::from(code.width());
+        }
+        let image = image::DynamicImage::ImageLuma8(combined);
+        let mut bytes = Vec::new();
+        image
+            .write_to(&mut Cursor::new(&mut bytes), format)
+            .unwrap();
+        bytes
+    }
+
+    #[test]
+    fn reading_png_and_jpeg_works() {
+        let epc = Epc::builder()
+            .version(Version::V1)
+            .character_set(CharacterSet::Iso8859_1)
+            .identification(Identification::Sct)
+            .bic("GENODEF1SLR")
+            .beneficiary("Bäckerei Müller")
+            .iban("DE90 8306 5408 0004 1042 42")
+            .amount("10.00")
+            .remittance(Remittance::Text("for the good cause".to_string()))
+            .build()
+            .unwrap();
+        for format in [ImageFormat::Png, ImageFormat::Jpeg] {
+            let image = image(&epc.to_bytes().unwrap(), format);
+            assert_eq!(Epc::from_image_bytes(&image), Ok(epc.clone()));
+        }
+    }
+
+    #[cfg(feature = "png")]
+    #[test]
+    fn reading_rendered_png_works() {
+        let epc = test_epc();
+        let png = epc.render_png(&crate::png::PngOptions::default()).unwrap();
+        assert_eq!(Epc::from_image_bytes(&png), Ok(epc));
+    }
+
+    #[cfg(feature = "qr")]
+    #[test]
+    fn reading_utf8_with_eci_works() {
+        let epc = Epc::builder()
+            .version(Version::V2)
+            .character_set(CharacterSet::UTF8)
+            .identification(Identification::Sct)
+            .beneficiary("Bäckerei Müller")
+            .iban("DE90 8306 5408 0004 1042 42")
+            .remittance(Remittance::Text("Grüße für 2 Brötchen".to_string()))
+            .build()
+            .unwrap();
+        let code = epc.to_qr_code().unwrap();
+        assert_eq!(code.eci(), Some(26));
+
+        let scale = 4;
+        let quiet_zone = crate::qr::QUIET_ZONE;
+        let width = ((code.size() + 2 * quiet_zone) * scale) as u32;
+        let image = image::GrayImage::from_fn(width, width, |x, y| {
+            let module = |v: u32| (v as usize / scale).checked_sub(quiet_zone);
+            match (module(x), module(y)) {
+                (Some(x), Some(y)) if code.is_dark(x, y) => Luma([0]),
+                _ => Luma([255]),
+            }
+        });
+        let mut png = Vec::new();
+        image
+            .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+            .unwrap();
+        assert_eq!(Epc::from_image_bytes(&png), Ok(epc));
+    }
+
+    #[test]
+    fn verifying_image_works() {
+        let epc = test_epc();
+        let path = std::env::temp_dir().join(format!(
+            "epcgen_verifying_image_works_{}.png",
+            std::process::id()
+        ));
+        std::fs::write(&path, image(&epc.to_bytes().unwrap(), ImageFormat::Png)).unwrap();
+        let same = epc.verify_image(&path);
+
+        let other = epc.to_builder().amount("100.00").build().unwrap();
+        let r = other.verify_image(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(same, Ok(()));
+        assert_eq!(r, Err(DecodeError::Mismatch(Box::new(epc))));
+    }
+
+    #[test]
+    fn image_without_epc_should_fail() {
+        let png = image(b"https://codeberg.org/dawe/epcgen", ImageFormat::Png);
+        assert_eq!(Epc::from_image_bytes(&png), Err(DecodeError::NoEpcCode));
+        assert!(matches!(
+            Epc::from_image_bytes(b"no image"),
+            Err(DecodeError::Image(_))
+        ));
+        assert!(matches!(
+            Epc::from_image("does/not/exist.png"),
+            Err(DecodeError::Image(_))
+        ));
+    }
+
+    #[test]
+    fn invalid_epc_should_fail() {
+        let png = image(b"BCD\n002\n1\nSCT\n\nCodeberg e.V.\nDE00", ImageFormat::Png);
+        assert!(matches!(
+            Epc::from_image_bytes(&png),
+            Err(DecodeError::Epc(_))
+        ));
+    }
+
+    #[test]
+    fn invalid_epc_next_to_valid_one_is_skipped() {
+        let epc = test_epc();
+        let invalid: &[u8] = b"BCD\n002\n1\nSCT\n\nCodeberg e.V.\nDE00";
+        let valid = epc.to_bytes().unwrap();
+        for payloads in [[invalid, &valid], [&valid, invalid]] {
+            let png = images(&payloads, ImageFormat::Png);
+            assert_eq!(Epc::from_image_bytes(&png), Ok(epc.clone()));
+        }
+    }
+}
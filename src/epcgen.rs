@@ -1,7 +1,25 @@
-use crate::ibanrf::iban;
+use crate::bic;
+use crate::charset;
 use crate::ibanrf::rf;
+use crate::purpose::{self, PurposeCategory};
+use crate::typed::{Missing, TypedBuilder};
+use crate::types::{BeneficiaryName, Bic, Iban, RemittanceText, RfReference};
+use std::borrow::Cow;
+use std::convert::Infallible;
 use std::error::Error;
 use std::fmt::Display;
+use std::str::FromStr;
+
+/// Maximum number of characters of the beneficiary name
+pub(crate) const MAX_BENEFICIARY_LEN: usize = 70;
+/// Maximum number of characters of the unstructured remittance information
+pub(crate) const MAX_REMITTANCE_TEXT_LEN: usize = 140;
+/// Maximum number of characters of the beneficiary to originator information
+const MAX_INFORMATION_LEN: usize = 70;
+/// Maximum number of bytes of the encoded payload
+pub(crate) const MAX_PAYLOAD_LEN: usize = 331;
+/// Number of bytes a QR code of version 10 with error correction level M can hold
+const LARGE_PAYLOAD_LEN: usize = 213;
 
 /// Service Tag
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -17,8 +35,20 @@ impl Display for ServiceTag {
     }
 }
 
+impl FromStr for ServiceTag {
+    type Err = EpcError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "BCD" => Ok(ServiceTag::Bcd),
+            _ => Err(EpcError::InvalidServiceTag),
+        }
+    }
+}
+
 /// Version of the EPC
 #[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Version {
     /// 001 - EWR plus Non-EWR
     V1,
@@ -35,38 +65,76 @@ impl Display for Version {
     }
 }
 
+impl FromStr for Version {
+    type Err = EpcError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "001" => Ok(Version::V1),
+            "002" => Ok(Version::V2),
+            _ => Err(EpcError::InvalidVersion),
+        }
+    }
+}
+
 /// Character set used to generate the QR code
 #[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CharacterSet {
     /// Encoded as value 1 in the EPC
     UTF8,
-    // todo
-    // Encoded as value 2 in the EPC
-    // Iso8859_1,
-    // Encoded as value 3 in the EPC
-    // Iso8859_2,
-    // Encoded as value 4 in the EPC
-    // Iso8859_4,
-    // Encoded as value 5 in the EPC
-    // Iso8859_5,
-    // Encoded as value 6 in the EPC
-    // Iso8859_7,
-    // Encoded as value 7 in the EPC
-    // Iso8859_10,
-    // Encoded as value 8 in the EPC
-    // Iso8859_15,
+    /// Encoded as value 2 in the EPC
+    Iso8859_1,
+    /// Encoded as value 3 in the EPC
+    Iso8859_2,
+    /// Encoded as value 4 in the EPC
+    Iso8859_4,
+    /// Encoded as value 5 in the EPC
+    Iso8859_5,
+    /// Encoded as value 6 in the EPC
+    Iso8859_7,
+    /// Encoded as value 7 in the EPC
+    Iso8859_10,
+    /// Encoded as value 8 in the EPC
+    Iso8859_15,
 }
 
 impl Display for CharacterSet {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             CharacterSet::UTF8 => write!(f, "1"),
+            CharacterSet::Iso8859_1 => write!(f, "2"),
+            CharacterSet::Iso8859_2 => write!(f, "3"),
+            CharacterSet::Iso8859_4 => write!(f, "4"),
+            CharacterSet::Iso8859_5 => write!(f, "5"),
+            CharacterSet::Iso8859_7 => write!(f, "6"),
+            CharacterSet::Iso8859_10 => write!(f, "7"),
+            CharacterSet::Iso8859_15 => write!(f, "8"),
+        }
+    }
+}
+
+impl FromStr for CharacterSet {
+    type Err = EpcError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(CharacterSet::UTF8),
+            "2" => Ok(CharacterSet::Iso8859_1),
+            "3" => Ok(CharacterSet::Iso8859_2),
+            "4" => Ok(CharacterSet::Iso8859_4),
+            "5" => Ok(CharacterSet::Iso8859_5),
+            "6" => Ok(CharacterSet::Iso8859_7),
+            "7" => Ok(CharacterSet::Iso8859_10),
+            "8" => Ok(CharacterSet::Iso8859_15),
+            _ => Err(EpcError::InvalidCharacterSet),
         }
     }
 }
 
 /// Identification code
 #[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Identification {
     /// SEPA Credit Transfer
     Sct,
@@ -83,8 +151,21 @@ impl Display for Identification {
     }
 }
 
+impl FromStr for Identification {
+    type Err = EpcError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "SCT" => Ok(Identification::Sct),
+            "INST" => Ok(Identification::Inst),
+            _ => Err(EpcError::InvalidIdentification),
+        }
+    }
+}
+
 /// Purpose of the SEPA credit transfer
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Purpose {
     /// Benefit
     Bene,
@@ -122,8 +203,175 @@ impl Display for Purpose {
     }
 }
 
+impl Purpose {
+    /// All codes of the ISO 20022 ExternalPurpose1Code list, sorted by code
+    pub fn all() -> impl Iterator<Item = Purpose> {
+        purpose::PURPOSE_CODES
+            .iter()
+            .map(|p| Purpose::from_code(p.code))
+    }
+
+    /// The code as written into the EPC
+    pub fn code(&self) -> &str {
+        match self {
+            Purpose::Bene => "BENE",
+            Purpose::Bonu => "BONU",
+            Purpose::Cbff => "CBFF",
+            Purpose::Char => "CHAR",
+            Purpose::Govt => "GOVT",
+            Purpose::Pens => "PENS",
+            Purpose::Sala => "SALA",
+            Purpose::SSBE => "SSBE",
+            Purpose::Custom(c) => c,
+        }
+    }
+
+    /// Whether the code is part of the ISO 20022 ExternalPurpose1Code list
+    pub fn is_known(&self) -> bool {
+        purpose::lookup(self.code()).is_some()
+    }
+
+    /// Short description of the code, if it is part of the ExternalPurpose1Code list
+    pub fn description(&self) -> Option<&'static str> {
+        purpose::lookup(self.code()).map(|p| p.description)
+    }
+
+    /// Category of the code, if it is part of the ExternalPurpose1Code list
+    pub fn category(&self) -> Option<PurposeCategory> {
+        purpose::lookup(self.code()).map(|p| p.category)
+    }
+
+    /// The named variant of a code, or a custom purpose
+    fn from_code(code: &str) -> Purpose {
+        match code {
+            "BENE" => Purpose::Bene,
+            "BONU" => Purpose::Bonu,
+            "CBFF" => Purpose::Cbff,
+            "CHAR" => Purpose::Char,
+            "GOVT" => Purpose::Govt,
+            "PENS" => Purpose::Pens,
+            "SALA" => Purpose::Sala,
+            "SSBE" => Purpose::SSBE,
+            _ => Purpose::Custom(code.to_string()),
+        }
+    }
+
+    /// Whether the code has the format of a purpose code, i.e. four uppercase letters, or is
+    /// part of the ExternalPurpose1Code list
+    fn is_well_formed(&self) -> bool {
+        let code = self.code();
+        (code.len() == 4 && code.chars().all(|c| c.is_ascii_uppercase())) || self.is_known()
+    }
+}
+
+impl FromStr for Purpose {
+    type Err = EpcError;
+
+    /// Parse a purpose code, using the named variants where available
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let purpose = Purpose::from_code(s);
+        if purpose.is_well_formed() {
+            Ok(purpose)
+        } else {
+            Err(EpcError::InvalidPurpose)
+        }
+    }
+}
+
+/// Amount of a SEPA credit transfer in Euro, from EUR0.01 up to EUR999999999.99
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "String", into = "String")
+)]
+pub struct Amount {
+    cents: u64,
+}
+
+impl Amount {
+    /// The smallest valid amount in cents
+    pub const MIN_CENTS: u64 = 1;
+    /// The largest valid amount in cents
+    pub const MAX_CENTS: u64 = 99_999_999_999;
+
+    /// Create an amount from Euro cents
+    pub fn from_cents(cents: u64) -> Result<Amount, EpcError> {
+        if (Self::MIN_CENTS..=Self::MAX_CENTS).contains(&cents) {
+            Ok(Amount { cents })
+        } else {
+            Err(EpcError::InvalidAmount)
+        }
+    }
+
+    /// The amount in Euro cents
+    pub fn cents(&self) -> u64 {
+        self.cents
+    }
+}
+
+impl Display for Amount {
+    /// Format the amount like the EPC expects it, e.g. `EUR10.50`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "EUR{}.{:02}", self.cents / 100, self.cents % 100)
+    }
+}
+
+impl FromStr for Amount {
+    type Err = EpcError;
+
+    /// Parse a decimal amount with at most two decimal places, optionally prefixed with `EUR`,
+    /// e.g. `"10"`, `"10.5"`, `"10.50"` or `"EUR10.50"`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.strip_prefix("EUR").unwrap_or(s);
+        let (i_part, d_part) = match s.split_once('.') {
+            Some((_, "")) => return Result::Err(EpcError::InvalidAmount),
+            Some((i_part, d_part)) => (i_part, d_part),
+            None => (s, ""),
+        };
+        if i_part.is_empty()
+            || i_part.len() > 9
+            || d_part.len() > 2
+            || !i_part
+                .chars()
+                .chain(d_part.chars())
+                .all(|c| c.is_ascii_digit())
+        {
+            return Result::Err(EpcError::InvalidAmount);
+        }
+        let euros: u64 = i_part.parse().map_err(|_| EpcError::InvalidAmount)?;
+        let cents: u64 = format!("{:0<2}", d_part)
+            .parse()
+            .map_err(|_| EpcError::InvalidAmount)?;
+        Amount::from_cents(euros * 100 + cents)
+    }
+}
+
+impl TryFrom<&str> for Amount {
+    type Error = EpcError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl TryFrom<String> for Amount {
+    type Error = EpcError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<Amount> for String {
+    fn from(amount: Amount) -> Self {
+        amount.to_string()
+    }
+}
+
 /// Remittance information
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Remittance {
     /// The structured RF creditor reference
     Reference(String),
@@ -140,10 +388,63 @@ impl Display for Remittance {
     }
 }
 
+/// The fields of an EPC
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Field {
+    /// Service Tag
+    ServiceTag,
+    /// Version
+    Version,
+    /// Character set
+    CharacterSet,
+    /// Identification code
+    Identification,
+    /// The BIC code of the Beneficiary PSP
+    Bic,
+    /// The name of the Beneficiary
+    Beneficiary,
+    /// The IBAN of the account of the Beneficiary
+    Iban,
+    /// Amount of the SEPA Credit Transfer in Euro
+    Amount,
+    /// Purpose of the SEPA Credit Transfer
+    Purpose,
+    /// The Remittance Information (structured or unstructured)
+    Remittance,
+    /// Beneficiary to Originator Information
+    Information,
+}
+
+impl Display for Field {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Field::ServiceTag => write!(f, "service tag"),
+            Field::Version => write!(f, "version"),
+            Field::CharacterSet => write!(f, "character set"),
+            Field::Identification => write!(f, "identification"),
+            Field::Bic => write!(f, "BIC"),
+            Field::Beneficiary => write!(f, "beneficiary"),
+            Field::Iban => write!(f, "IBAN"),
+            Field::Amount => write!(f, "amount"),
+            Field::Purpose => write!(f, "purpose"),
+            Field::Remittance => write!(f, "remittance"),
+            Field::Information => write!(f, "information"),
+        }
+    }
+}
+
 /// Models an EPC
-#[derive(Debug, PartialEq)]
+///
+/// With the `serde` feature, deserializing an EPC validates it like [`Builder::build`].
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "EpcData")
+)]
 pub struct Epc {
     /// Service Tag
+    #[cfg_attr(feature = "serde", serde(skip_serializing))]
     service_tag: ServiceTag,
     /// Version
     version: Version,
@@ -152,13 +453,13 @@ pub struct Epc {
     /// Identification code
     identification: Identification,
     /// The BIC code of the Beneficiary PSP
-    bic: Option<String>,
+    bic: Option<Bic>,
     /// The name of the account of the Beneficiary
     beneficiary: String,
     /// The IBAN of the account of the Beneficiary
-    iban: String,
+    iban: Iban,
     /// Amount of the SEPA Credit Transfer in Euro
-    amount: Option<String>,
+    amount: Option<Amount>,
     /// Purpose of the SEPA Credit Transfer
     purpose: Option<Purpose>,
     /// The Remittance Information (structured or unstructured)
@@ -171,39 +472,460 @@ impl<'a> Epc {
     pub fn builder() -> Builder<'a> {
         Builder::default()
     }
+
+    /// Create a builder checking at compile time that all mandatory fields are set
+    pub fn typed_builder() -> TypedBuilder<'a, Missing, Missing, Missing, Missing, Missing> {
+        TypedBuilder::default()
+    }
+
+    /// Parse an EPC from the raw bytes of a QR code payload, decoded in the character set it
+    /// declares
+    pub fn parse(bytes: &[u8]) -> Result<Epc, EpcError> {
+        let character_set = bytes
+            .split(|b| *b == b'\n')
+            .nth(2)
+            .ok_or(EpcError::MalformedPayload)?;
+        let character_set = character_set.strip_suffix(b"\r").unwrap_or(character_set);
+        let character_set = std::str::from_utf8(character_set)
+            .map_err(|_| EpcError::InvalidCharacterSet)?
+            .parse()?;
+        let payload = charset::decode(character_set, bytes).ok_or(EpcError::InvalidEncoding)?;
+        payload.parse()
+    }
+
+    /// Encode the payload in the configured character set
+    pub fn to_bytes(&self) -> Result<Vec<u8>, EpcError> {
+        let mut bytes = Vec::new();
+        for (i, (field, line)) in self.lines().into_iter().enumerate() {
+            if i > 0 {
+                bytes.push(b'\n');
+            }
+            let encoded = charset::encode(self.character_set, &line)
+                .map_err(|character| EpcError::UnencodableCharacter { field, character })?;
+            bytes.extend(encoded);
+        }
+        Ok(bytes)
+    }
+
+    /// Legal but risky properties of the EPC
+    fn warnings(&self) -> Vec<Warning> {
+        let mut warnings = Vec::new();
+        if self.version == Version::V2 && self.bic.is_some() {
+            warnings.push(Warning::BICInVersion2);
+        }
+        if let Some(bic) = &self.bic
+            && !bic::accepts_iban_country(bic.country(), self.iban.country())
+        {
+            warnings.push(Warning::BICCountryMismatch);
+        }
+        if self.identification == Identification::Inst && self.amount.is_none() {
+            warnings.push(Warning::InstantWithoutAmount);
+        }
+        if !is_sepa_latin(&self.beneficiary) {
+            warnings.push(Warning::NonSepaCharacters {
+                field: Field::Beneficiary,
+            });
+        }
+        if let Some(Remittance::Text(text)) = &self.remittance {
+            if !is_sepa_latin(text) {
+                warnings.push(Warning::NonSepaCharacters {
+                    field: Field::Remittance,
+                });
+            }
+            if rf::is_valid(text) {
+                warnings.push(Warning::TextLooksLikeReference);
+            }
+        }
+        let bytes = self.to_bytes().map(|b| b.len()).unwrap_or_default();
+        if bytes > LARGE_PAYLOAD_LEN {
+            warnings.push(Warning::LargePayload { bytes });
+        }
+        warnings
+    }
+
+    /// The lines of the payload together with the field they carry
+    fn lines(&self) -> [(Field, String); 12] {
+        let (reference, text) = match &self.remittance {
+            Some(Remittance::Reference(r)) => (r.clone(), String::new()),
+            Some(Remittance::Text(t)) => (String::new(), t.clone()),
+            None => (String::new(), String::new()),
+        };
+        [
+            (Field::ServiceTag, self.service_tag.to_string()),
+            (Field::Version, self.version.to_string()),
+            (Field::CharacterSet, self.character_set.to_string()),
+            (Field::Identification, self.identification.to_string()),
+            (
+                Field::Bic,
+                self.bic.as_ref().map(|b| b.to_string()).unwrap_or_default(),
+            ),
+            (Field::Beneficiary, self.beneficiary.clone()),
+            (Field::Iban, self.iban.to_string()),
+            (
+                Field::Amount,
+                self.amount.map(|a| a.to_string()).unwrap_or_default(),
+            ),
+            (
+                Field::Purpose,
+                self.purpose
+                    .as_ref()
+                    .map(|p| p.to_string())
+                    .unwrap_or_default(),
+            ),
+            (Field::Remittance, reference),
+            (Field::Remittance, text),
+            (
+                Field::Information,
+                self.information.clone().unwrap_or_default(),
+            ),
+        ]
+    }
+
+    /// The Service Tag
+    pub fn service_tag(&self) -> ServiceTag {
+        self.service_tag
+    }
+
+    /// The Version
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
+    /// The Character set
+    pub fn character_set(&self) -> CharacterSet {
+        self.character_set
+    }
+
+    /// The Identification code
+    pub fn identification(&self) -> Identification {
+        self.identification
+    }
+
+    /// The BIC code of the Beneficiary PSP
+    pub fn bic(&self) -> Option<&str> {
+        self.bic.as_ref().map(|b| b.as_ref())
+    }
+
+    /// The name of the Beneficiary
+    pub fn beneficiary(&self) -> &str {
+        &self.beneficiary
+    }
+
+    /// The IBAN of the account of the Beneficiary, in electronic format
+    pub fn iban(&self) -> &str {
+        self.iban.as_ref()
+    }
+
+    /// Amount of the SEPA Credit Transfer in Euro
+    pub fn amount(&self) -> Option<Amount> {
+        self.amount
+    }
+
+    /// Purpose of the SEPA Credit Transfer
+    pub fn purpose(&self) -> Option<&Purpose> {
+        self.purpose.as_ref()
+    }
+
+    /// The Remittance Information (structured or unstructured)
+    pub fn remittance(&self) -> Option<&Remittance> {
+        self.remittance.as_ref()
+    }
+
+    /// Beneficiary to Originator Information
+    pub fn information(&self) -> Option<&str> {
+        self.information.as_deref()
+    }
+
+    /// Create a builder with all fields of this EPC, e.g. to build a modified copy
+    pub fn to_builder(&self) -> Builder<'_> {
+        Builder {
+            service_tag: self.service_tag,
+            version: Some(self.version),
+            character_set: Some(self.character_set),
+            identification: Some(self.identification),
+            bic: self.bic.clone().map(Result::Ok),
+            beneficiary: Some(Cow::Borrowed(&self.beneficiary)),
+            beneficiary_validated: true,
+            iban: Some(Result::Ok(self.iban.clone())),
+            amount: self.amount.map(Result::Ok),
+            purpose: self.purpose.clone(),
+            remittance: self.remittance.clone(),
+            remittance_validated: true,
+            information: self.information.as_deref().map(Cow::Borrowed),
+            control_characters: ControlCharacters::default(),
+            purpose_codes: PurposeCodes::default(),
+        }
+    }
+
+    /// Turn this EPC into an owned builder with all its fields
+    pub fn into_builder(self) -> Builder<'static> {
+        Builder {
+            service_tag: self.service_tag,
+            version: Some(self.version),
+            character_set: Some(self.character_set),
+            identification: Some(self.identification),
+            bic: self.bic.map(Result::Ok),
+            beneficiary: Some(Cow::Owned(self.beneficiary)),
+            beneficiary_validated: true,
+            iban: Some(Result::Ok(self.iban)),
+            amount: self.amount.map(Result::Ok),
+            purpose: self.purpose,
+            remittance: self.remittance,
+            remittance_validated: true,
+            information: self.information.map(Cow::Owned),
+            control_characters: ControlCharacters::default(),
+            purpose_codes: PurposeCodes::default(),
+        }
+    }
+}
+
+impl FromStr for Epc {
+    type Err = EpcError;
+
+    /// Parse an EPC from a QR code payload, validating it like [`Builder::build`]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let payload = s.strip_suffix('\n').unwrap_or(s);
+        let lines: Vec<&str> = payload
+            .split('\n')
+            .map(|l| l.strip_suffix('\r').unwrap_or(l))
+            .collect();
+        // Trailing empty lines may be omitted, everything up to the IBAN is mandatory
+        if lines.len() < 7 || lines.len() > 12 {
+            return Result::Err(EpcError::MalformedPayload);
+        }
+        let line = |i: usize| lines.get(i).copied().filter(|l| !l.is_empty());
+
+        lines[0].parse::<ServiceTag>()?;
+        let mut builder = Epc::builder()
+            .version(lines[1].parse()?)
+            .character_set(lines[2].parse()?)
+            .identification(lines[3].parse()?);
+        if let Some(bic) = line(4) {
+            builder = builder.bic(bic);
+        }
+        if let Some(beneficiary) = line(5) {
+            builder = builder.beneficiary(beneficiary);
+        }
+        if let Some(iban) = line(6) {
+            builder = builder.iban(iban);
+        }
+        if let Some(amount) = line(7) {
+            builder = builder.amount(amount);
+        }
+        if let Some(purpose) = line(8) {
+            builder = builder.purpose(purpose.parse()?);
+        }
+        match (line(9), line(10)) {
+            (Some(_), Some(_)) => return Result::Err(EpcError::MalformedPayload),
+            (Some(r), None) => builder = builder.remittance(Remittance::Reference(r.to_string())),
+            (None, Some(t)) => builder = builder.remittance(Remittance::Text(t.to_string())),
+            (None, None) => (),
+        }
+        if let Some(information) = line(11) {
+            builder = builder.information(information);
+        }
+        builder.build()
+    }
+}
+
+/// The serialized fields of an EPC, before they are validated
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct EpcData {
+    version: Version,
+    character_set: CharacterSet,
+    identification: Identification,
+    bic: Option<String>,
+    beneficiary: String,
+    iban: String,
+    amount: Option<String>,
+    purpose: Option<Purpose>,
+    remittance: Option<Remittance>,
+    information: Option<String>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<EpcData> for Epc {
+    type Error = EpcError;
+
+    fn try_from(data: EpcData) -> Result<Self, Self::Error> {
+        let mut builder = Epc::builder()
+            .version(data.version)
+            .character_set(data.character_set)
+            .identification(data.identification)
+            .beneficiary(data.beneficiary)
+            .iban(data.iban);
+        if let Some(bic) = data.bic {
+            builder = builder.bic(bic);
+        }
+        if let Some(amount) = data.amount {
+            builder = builder.amount(amount);
+        }
+        if let Some(purpose) = data.purpose {
+            builder = builder.purpose(purpose);
+        }
+        if let Some(remittance) = data.remittance {
+            builder = builder.remittance(remittance);
+        }
+        if let Some(information) = data.information {
+            builder = builder.information(information);
+        }
+        builder.build()
+    }
 }
 
 impl Display for Epc {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let empty_string = "".to_string();
-        writeln!(f, "{}", self.service_tag)?;
-        writeln!(f, "{}", self.version)?;
-        writeln!(f, "{}", self.character_set)?;
-        writeln!(f, "{}", self.identification)?;
-        let bic = self.bic.as_ref().unwrap_or(&empty_string);
-        writeln!(f, "{}", bic)?;
-        writeln!(f, "{}", self.beneficiary)?;
-        writeln!(f, "{}", self.iban)?;
-        let amount = self.amount.as_ref().unwrap_or(&empty_string);
-        writeln!(f, "{}", amount)?;
-        let purpose = self
-            .purpose
-            .as_ref()
-            .map(|p| p.to_string())
-            .unwrap_or("".to_string());
-        writeln!(f, "{}", purpose)?;
-        match &self.remittance {
-            Some(Remittance::Reference(r)) => writeln!(f, "{}\n", r),
-            Some(Remittance::Text(r)) => writeln!(f, "\n{}", r),
-            None => writeln!(f, "\n"),
-        }?;
-        let information = self.information.as_ref().unwrap_or(&empty_string);
-        write!(f, "{}", information)
+        for (i, (_, line)) in self.lines().iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+/// A problem found when validating a [`Builder`]
+#[derive(Debug, PartialEq, Clone)]
+pub struct ValidationIssue {
+    /// The field the problem belongs to, `None` for problems of the payload as a whole
+    pub field: Option<Field>,
+    /// The problem
+    pub error: EpcError,
+}
+
+/// All problems found when validating a [`Builder`], in the order [`Builder::build`] checks
+/// them
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct ValidationReport {
+    issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    /// Whether no problems were found
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// All found problems
+    pub fn issues(&self) -> &[ValidationIssue] {
+        &self.issues
+    }
+
+    /// All found errors
+    pub fn errors(&self) -> impl Iterator<Item = &EpcError> {
+        self.issues.iter().map(|i| &i.error)
+    }
+
+    /// The errors belonging to the given field
+    pub fn field_errors(&self, field: Field) -> impl Iterator<Item = &EpcError> {
+        self.issues
+            .iter()
+            .filter(move |i| i.field == Some(field))
+            .map(|i| &i.error)
+    }
+
+    fn push(&mut self, field: Option<Field>, error: EpcError) {
+        self.issues.push(ValidationIssue { field, error });
+    }
+
+    /// Record the error of a check, if any, and return its value otherwise
+    fn check<T>(&mut self, field: Field, result: Result<T, EpcError>) -> Option<T> {
+        result.map_err(|error| self.push(Some(field), error)).ok()
+    }
+}
+
+impl Display for ValidationReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, issue) in self.issues.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", issue.error)?;
+        }
+        Ok(())
+    }
+}
+
+/// Treatment of control characters like CR and LF, as well as the Unicode line and paragraph
+/// separators U+2028 and U+2029, in free-text fields, which would otherwise break the line
+/// structure of the payload
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum ControlCharacters {
+    /// Fail with [`EpcError::ControlCharacter`]
+    #[default]
+    Reject,
+    /// Replace each run of control characters and the whitespace around it with a single
+    /// space, dropping it entirely at the start and the end of the value
+    Sanitize,
+}
+
+/// Purpose codes accepted by [`Builder::build`]
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum PurposeCodes {
+    /// Accept the codes of the ISO 20022 ExternalPurpose1Code list and any other code of four
+    /// uppercase letters
+    #[default]
+    Lenient,
+    /// Only accept the codes of the ExternalPurpose1Code list
+    Strict,
+}
+
+/// Legal but risky properties of an EPC, which banking apps might handle badly
+#[derive(Debug, PartialEq, Clone)]
+pub enum Warning {
+    /// A BIC is set although the configured Version doesn't need it
+    BICInVersion2,
+    /// The country of the BIC differs from the one of the IBAN, which is unusual apart from
+    /// territories using the IBANs of another country
+    BICCountryMismatch,
+    /// An instant credit transfer without an amount
+    InstantWithoutAmount,
+    /// The unstructured remittance text is a valid RF creditor reference
+    TextLooksLikeReference,
+    /// The field contains characters outside of the SEPA Latin character set, which banks might
+    /// replace or reject
+    NonSepaCharacters { field: Field },
+    /// The payload needs a QR code of version 11 or above, which is hard to scan when printed
+    /// small
+    LargePayload { bytes: usize },
+}
+
+impl Display for Warning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Warning::BICInVersion2 => write!(f, "BIC is set but not needed in Version 002"),
+            Warning::BICCountryMismatch => {
+                write!(f, "The countries of the BIC and the IBAN differ")
+            }
+            Warning::InstantWithoutAmount => {
+                write!(f, "Instant credit transfer without an amount")
+            }
+            Warning::TextLooksLikeReference => {
+                write!(f, "Remittance text is a structured RF creditor reference")
+            }
+            Warning::NonSepaCharacters { field } => write!(
+                f,
+                "The {} contains characters outside of the SEPA character set",
+                field
+            ),
+            Warning::LargePayload { bytes } => {
+                write!(f, "Large payload of {} bytes needs a big QR code", bytes)
+            }
+        }
     }
 }
 
+/// Whether the value only consists of characters of the SEPA Latin character set
+fn is_sepa_latin(value: &str) -> bool {
+    value
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || "/-?:().,'+ ".contains(c))
+}
+
 /// Possible errors when generating an EPC
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum EpcError {
     /// Version not set
     MissingVersion,
@@ -226,7 +948,36 @@ pub enum EpcError {
     /// Given remittance reference is invalid
     InvalidRemittanceReference,
     /// Given remittance text is too long
+    #[deprecated(note = "no longer returned, too long fields fail with `FieldTooLong`")]
     RemittanceTextTooLong,
+    /// Given field exceeds its maximum number of characters
+    FieldTooLong {
+        field: Field,
+        max: usize,
+        actual: usize,
+    },
+    /// Given BIC doesn't have 8 or 11 characters
+    InvalidBICLength { actual: usize },
+    /// Given BIC is invalid
+    InvalidBIC,
+    /// The encoded payload exceeds its maximum number of bytes
+    PayloadTooLong { max: usize, actual: usize },
+    /// Given field contains control characters like line breaks
+    ControlCharacter { field: Field },
+    /// Payload doesn't consist of the expected lines
+    MalformedPayload,
+    /// Payload isn't correctly encoded
+    InvalidEncoding,
+    /// Given service tag is invalid
+    InvalidServiceTag,
+    /// Given version is invalid
+    InvalidVersion,
+    /// Given character set is invalid
+    InvalidCharacterSet,
+    /// Given identification is invalid
+    InvalidIdentification,
+    /// A character of the field can't be encoded in the configured character set
+    UnencodableCharacter { field: Field, character: char },
 }
 
 impl Display for EpcError {
@@ -246,11 +997,46 @@ impl Display for EpcError {
             EpcError::InvalidRemittanceReference => {
                 write!(f, "Invalid structured RF creditor reference")
             }
+            #[allow(deprecated)]
             EpcError::RemittanceTextTooLong => write!(f, "Remittance text too long (max len 140)"),
+            EpcError::FieldTooLong { field, max, actual } => {
+                write!(
+                    f,
+                    "The {} is too long ({} > max len {})",
+                    field, actual, max
+                )
+            }
+            EpcError::InvalidBICLength { actual } => {
+                write!(f, "Invalid BIC length {} (expected 8 or 11)", actual)
+            }
+            EpcError::InvalidBIC => write!(f, "Invalid BIC"),
+            EpcError::PayloadTooLong { max, actual } => {
+                write!(f, "Payload too long ({} > max {} bytes)", actual, max)
+            }
+            EpcError::ControlCharacter { field } => {
+                write!(f, "The {} contains control characters", field)
+            }
+            EpcError::MalformedPayload => write!(f, "Malformed payload"),
+            EpcError::InvalidEncoding => write!(f, "Invalid payload encoding"),
+            EpcError::InvalidServiceTag => write!(f, "Invalid service tag"),
+            EpcError::InvalidVersion => write!(f, "Invalid version"),
+            EpcError::InvalidCharacterSet => write!(f, "Invalid character set"),
+            EpcError::InvalidIdentification => write!(f, "Invalid identification"),
+            EpcError::UnencodableCharacter { field, character } => write!(
+                f,
+                "Character '{}' in {} can't be encoded in the configured character set",
+                character, field
+            ),
         }
     }
 }
 
+impl From<Infallible> for EpcError {
+    fn from(infallible: Infallible) -> Self {
+        match infallible {}
+    }
+}
+
 impl Error for EpcError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         None
@@ -258,6 +1044,10 @@ impl Error for EpcError {
 }
 
 /// Builder for EPCs
+///
+/// The text fields can be given as borrowed `&str` or as owned `String`. A `Builder<'static>`
+/// owns all of its data, so it can be stored in structs or sent to other threads.
+#[derive(Debug, Clone)]
 pub struct Builder<'a> {
     /// Service Tag
     service_tag: ServiceTag,
@@ -268,19 +1058,27 @@ pub struct Builder<'a> {
     /// Identification code
     identification: Option<Identification>,
     /// The BIC code of the Beneficiary PSP
-    bic: Option<&'a str>,
+    bic: Option<Result<Bic, EpcError>>,
     /// The name of the account of the Beneficiary
-    beneficiary: Option<&'a str>,
+    beneficiary: Option<Cow<'a, str>>,
+    /// Whether the beneficiary has been validated already, e.g. as a [`BeneficiaryName`]
+    beneficiary_validated: bool,
     /// The IBAN of the account of the Beneficiary
-    iban: Option<String>,
+    iban: Option<Result<Iban, EpcError>>,
     /// Amount of the SEPA Credit Transfer in Euro
-    amount: Option<&'a str>,
+    amount: Option<Result<Amount, EpcError>>,
     /// Purpose of the SEPA Credit Transfer
     purpose: Option<Purpose>,
     /// The Remittance Information (structured or unstructured)
     remittance: Option<Remittance>,
+    /// Whether the remittance has been validated already, e.g. as an [`RfReference`]
+    remittance_validated: bool,
     /// Beneficiary to Originator Information
-    information: Option<&'a str>,
+    information: Option<Cow<'a, str>>,
+    /// Treatment of control characters in free-text fields
+    control_characters: ControlCharacters,
+    /// Accepted purpose codes
+    purpose_codes: PurposeCodes,
 }
 
 impl<'a> Builder<'a> {
@@ -292,11 +1090,15 @@ impl<'a> Builder<'a> {
             identification: None,
             bic: None,
             beneficiary: None,
+            beneficiary_validated: false,
             iban: None,
             amount: None,
             purpose: None,
             remittance: None,
+            remittance_validated: false,
             information: None,
+            control_characters: ControlCharacters::default(),
+            purpose_codes: PurposeCodes::default(),
         }
     }
 
@@ -318,150 +1120,319 @@ impl<'a> Builder<'a> {
         self
     }
 
-    /// Set the BIC (Business Identifier Code) of the recipient bank
-    pub fn bic(mut self, bic: &'a str) -> Self {
-        self.bic = Some(bic);
+    /// Set the BIC (Business Identifier Code) of the recipient bank, either as a [`Bic`] or as a
+    /// string
+    pub fn bic<B: TryInto<Bic>>(mut self, bic: B) -> Self
+    where
+        B::Error: Into<EpcError>,
+    {
+        self.bic = Some(bic.try_into().map_err(Into::into));
         self
     }
 
     /// Set the name of the beneficiary
-    pub fn beneficiary(mut self, beneficiary: &'a str) -> Self {
-        self.beneficiary = Some(beneficiary);
+    pub fn beneficiary(mut self, beneficiary: impl Into<Cow<'a, str>>) -> Self {
+        self.beneficiary = Some(beneficiary.into());
+        self.beneficiary_validated = false;
+        self
+    }
+
+    /// Set the name of the beneficiary, which isn't checked again when building
+    pub fn beneficiary_name(mut self, beneficiary: BeneficiaryName) -> Self {
+        self.beneficiary = Some(Cow::Owned(beneficiary.into()));
+        self.beneficiary_validated = true;
         self
     }
 
-    /// Set the IBAN of the beneficiary
-    pub fn iban(mut self, iban: &'a str) -> Self {
-        self.iban = Some(iban.replace(" ", ""));
+    /// Set the IBAN of the beneficiary, either as an [`Iban`] or as a string in electronic or
+    /// print format
+    pub fn iban<I: TryInto<Iban>>(mut self, iban: I) -> Self {
+        self.iban = Some(iban.try_into().map_err(|_| EpcError::InvalidIBAN));
         self
     }
 
-    /// Set the amount of the transfer
-    pub fn amount(mut self, amount: &'a str) -> Self {
-        self.amount = Some(amount);
+    /// Set the amount of the transfer, either as an [`Amount`] or as a string like `"10.50"`
+    pub fn amount<A: TryInto<Amount>>(mut self, amount: A) -> Self {
+        self.amount = Some(amount.try_into().map_err(|_| EpcError::InvalidAmount));
         self
     }
 
-    /// Set the purpose code
+    /// Set the purpose code, using the named variant for a custom code that has one
     pub fn purpose(mut self, purpose: Purpose) -> Self {
-        self.purpose = Some(purpose);
+        self.purpose = Some(match purpose {
+            Purpose::Custom(code) => Purpose::from_code(&code),
+            purpose => purpose,
+        });
         self
     }
 
-    /// Set the Remittance (reference)
-    pub fn remittance(mut self, remittance: Remittance) -> Self {
-        self.remittance = match remittance {
+    /// Set the Remittance, either as a [`Remittance`], an [`RfReference`] or a
+    /// [`RemittanceText`]
+    ///
+    /// An empty text unsets the remittance, as it can't be told apart from a missing one in the
+    /// payload. Use [`Builder::rf_reference`] or [`Builder::remittance_text`] to skip checking
+    /// validated values again.
+    pub fn remittance(mut self, remittance: impl Into<Remittance>) -> Self {
+        self.remittance = match remittance.into() {
             Remittance::Reference(r) => Some(Remittance::Reference(r.replace(" ", ""))),
-            Remittance::Text(_) => Some(remittance),
+            Remittance::Text(t) if t.is_empty() => None,
+            remittance @ Remittance::Text(_) => Some(remittance),
         };
+        self.remittance_validated = false;
         self
     }
 
-    /// Set the Beneficiary to Originator information
-    pub fn information(mut self, information: &'a str) -> Self {
-        self.information = Some(information);
+    /// Set a structured RF creditor reference, which isn't checked again when building
+    pub fn rf_reference(mut self, reference: RfReference) -> Self {
+        self.remittance = Some(reference.into());
+        self.remittance_validated = true;
         self
     }
 
-    /// Build the resulting EPC
-    pub fn build(&'_ self) -> Result<Epc, EpcError> {
-        let version = if let Some(version) = self.version {
-            version
-        } else {
-            return Result::Err(EpcError::MissingVersion);
-        };
+    /// Set an unstructured remittance text, which isn't checked again when building
+    ///
+    /// An empty text unsets the remittance like in [`Builder::remittance`].
+    pub fn remittance_text(mut self, text: RemittanceText) -> Self {
+        self.remittance = Some(text.into()).filter(|r| r != &Remittance::Text(String::new()));
+        self.remittance_validated = true;
+        self
+    }
 
-        let character_set = if let Some(character_set) = self.character_set {
-            character_set
-        } else {
-            return Result::Err(EpcError::MissingCharacterSet);
-        };
+    /// Set the Beneficiary to Originator information, an empty one unsets it
+    pub fn information(mut self, information: impl Into<Cow<'a, str>>) -> Self {
+        let information = information.into();
+        self.information = (!information.is_empty()).then_some(information);
+        self
+    }
 
-        let identification = if let Some(identification) = self.identification {
-            identification
-        } else {
-            return Result::Err(EpcError::MissingIdentification);
-        };
+    /// Set how control characters like line breaks in free-text fields are treated
+    pub fn control_characters(mut self, control_characters: ControlCharacters) -> Self {
+        self.control_characters = control_characters;
+        self
+    }
 
-        if self.bic.is_none() && version != Version::V2 {
-            return Result::Err(EpcError::BICRequiredInConfiguredVersion);
-        }
+    /// Set which purpose codes are accepted
+    pub fn purpose_codes(mut self, purpose_codes: PurposeCodes) -> Self {
+        self.purpose_codes = purpose_codes;
+        self
+    }
 
-        let beneficiary = if let Some(beneficiary) = self.beneficiary {
-            beneficiary
-        } else {
-            return Result::Err(EpcError::MissingBeneficiary);
+    /// Build the resulting EPC
+    ///
+    /// Legal but risky input like a BIC from another country than the IBAN doesn't fail the
+    /// build, [`Builder::build_with_warnings`] reports it.
+    pub fn build(&'_ self) -> Result<Epc, EpcError> {
+        self.check()
+            .map_err(|mut report| report.issues.remove(0).error)
+    }
+
+    /// Build the resulting EPC together with warnings about legal but risky properties
+    pub fn build_with_warnings(&'_ self) -> Result<(Epc, Vec<Warning>), EpcError> {
+        let epc = self.build()?;
+        let warnings = epc.warnings();
+        Result::Ok((epc, warnings))
+    }
+
+    /// Validate all fields, reporting every problem instead of only the first one like
+    /// [`Builder::build`]
+    pub fn validate(&'_ self) -> ValidationReport {
+        self.check().err().unwrap_or_default()
+    }
+
+    /// Check all fields and build the EPC if there are no problems
+    fn check(&'_ self) -> Result<Epc, ValidationReport> {
+        let mut report = ValidationReport::default();
+        let version = report.check(Field::Version, self.version.ok_or(EpcError::MissingVersion));
+        let character_set = report.check(
+            Field::CharacterSet,
+            self.character_set.ok_or(EpcError::MissingCharacterSet),
+        );
+        let identification = report.check(
+            Field::Identification,
+            self.identification.ok_or(EpcError::MissingIdentification),
+        );
+        let bic = report.check(Field::Bic, self.check_bic(version));
+        let beneficiary = report.check(Field::Beneficiary, self.check_beneficiary());
+        let iban = report.check(Field::Iban, self.check_iban());
+        let amount = report.check(Field::Amount, self.amount.clone().transpose());
+        let purpose = report.check(Field::Purpose, self.check_purpose());
+        let remittance = report.check(Field::Remittance, self.check_remittance());
+        let information = report.check(Field::Information, self.check_information());
+
+        let (
+            Some(version),
+            Some(character_set),
+            Some(identification),
+            Some(bic),
+            Some(beneficiary),
+            Some(iban),
+            Some(amount),
+            Some(purpose),
+            Some(remittance),
+            Some(information),
+            true,
+        ) = (
+            version,
+            character_set,
+            identification,
+            bic,
+            beneficiary,
+            iban,
+            amount,
+            purpose,
+            remittance,
+            information,
+            report.is_ok(),
+        )
+        else {
+            return Result::Err(report);
         };
 
-        let iban = if let Some(iban) = self.iban.clone() {
-            if iban::is_valid(iban.as_str()) {
-                iban
-            } else {
-                return Result::Err(EpcError::InvalidIBAN);
-            }
-        } else {
-            return Result::Err(EpcError::MissingIBAN);
+        let epc = Epc {
+            service_tag: self.service_tag,
+            version,
+            character_set,
+            identification,
+            bic,
+            beneficiary: beneficiary.into_owned(),
+            iban,
+            amount,
+            purpose,
+            remittance,
+            information: information.map(|s| s.into_owned()),
         };
 
-        let amount = if let Some(amount) = self.amount {
-            let ok = amount.chars().all(|c| c.is_ascii_digit() || c == '.');
-            if ok
-                && let Some((i_part, d_part)) = amount.split_once(".")
-                && i_part.len() <= 9
-                && d_part.len() == 2
-            {
-                match (i_part.parse::<i128>(), d_part.parse::<i32>()) {
-                    (Ok(i_part), Ok(d_part)) if i_part == 0 && (1..=99).contains(&d_part) => {
-                        self.amount
-                    }
-                    (Ok(i_part), Ok(d_part))
-                        if (1..=999999999).contains(&i_part) && (0..=99).contains(&d_part) =>
-                    {
-                        self.amount
-                    }
-                    (_, _) => return Result::Err(EpcError::InvalidAmount),
-                }
-            } else {
-                return Result::Err(EpcError::InvalidAmount);
+        match epc.to_bytes() {
+            Result::Err(error @ EpcError::UnencodableCharacter { field, .. }) => {
+                report.push(Some(field), error);
             }
+            Result::Err(error) => report.push(None, error),
+            Result::Ok(bytes) if bytes.len() > MAX_PAYLOAD_LEN => report.push(
+                None,
+                EpcError::PayloadTooLong {
+                    max: MAX_PAYLOAD_LEN,
+                    actual: bytes.len(),
+                },
+            ),
+            Result::Ok(_) => (),
+        }
+
+        if report.is_ok() {
+            Result::Ok(epc)
         } else {
-            None
-        };
+            Result::Err(report)
+        }
+    }
 
+    fn check_bic(&self, version: Option<Version>) -> Result<Option<Bic>, EpcError> {
+        match &self.bic {
+            Some(bic) => bic.clone().map(Some),
+            None => match version {
+                Some(version) if version != Version::V2 => {
+                    Result::Err(EpcError::BICRequiredInConfiguredVersion)
+                }
+                _ => Result::Ok(None),
+            },
+        }
+    }
+
+    fn check_beneficiary(&self) -> Result<Cow<'_, str>, EpcError> {
+        let beneficiary = self
+            .beneficiary
+            .as_deref()
+            .filter(|b| !b.is_empty())
+            .ok_or(EpcError::MissingBeneficiary)?;
+        if self.beneficiary_validated {
+            return Result::Ok(Cow::Borrowed(beneficiary));
+        }
+        let beneficiary = self.check_control_characters(Field::Beneficiary, beneficiary)?;
+        if beneficiary.is_empty() {
+            return Result::Err(EpcError::MissingBeneficiary);
+        }
+        check_len(Field::Beneficiary, &beneficiary, MAX_BENEFICIARY_LEN)?;
+        Result::Ok(beneficiary)
+    }
+
+    fn check_iban(&self) -> Result<Iban, EpcError> {
+        self.iban
+            .clone()
+            .unwrap_or(Result::Err(EpcError::MissingIBAN))
+    }
+
+    fn check_purpose(&self) -> Result<Option<Purpose>, EpcError> {
         match &self.purpose {
-            Some(Purpose::Custom(p))
-                if p.len() != 4 || p.chars().any(|c| !c.is_ascii_uppercase()) =>
-            {
-                return Result::Err(EpcError::InvalidPurpose);
+            Some(purpose) if !purpose.is_well_formed() => Result::Err(EpcError::InvalidPurpose),
+            Some(purpose) if self.purpose_codes == PurposeCodes::Strict && !purpose.is_known() => {
+                Result::Err(EpcError::InvalidPurpose)
             }
-            _ => (),
+            purpose => Result::Ok(purpose.clone()),
         }
+    }
 
+    fn check_remittance(&self) -> Result<Option<Remittance>, EpcError> {
+        if self.remittance_validated {
+            return Result::Ok(self.remittance.clone());
+        }
         match &self.remittance {
-            Some(Remittance::Reference(s)) => {
-                if !rf::is_valid(s) {
-                    return Result::Err(EpcError::InvalidRemittanceReference);
-                }
+            Some(Remittance::Reference(s)) if !rf::is_valid(s) => {
+                Result::Err(EpcError::InvalidRemittanceReference)
             }
-            Some(Remittance::Text(s)) if s.len() > 140 => {
-                return Result::Err(EpcError::RemittanceTextTooLong);
+            Some(Remittance::Text(s)) => {
+                let s = self.check_control_characters(Field::Remittance, s)?;
+                check_len(Field::Remittance, &s, MAX_REMITTANCE_TEXT_LEN)?;
+                Result::Ok((!s.is_empty()).then(|| Remittance::Text(s.into_owned())))
             }
-            _ => (),
+            remittance => Result::Ok(remittance.clone()),
         }
+    }
 
-        Result::Ok(Epc {
-            service_tag: self.service_tag,
-            version,
-            character_set,
-            identification,
-            bic: self.bic.map(|s| s.to_string()),
-            beneficiary: beneficiary.to_string(),
-            iban,
-            amount: amount.map(|s| s.to_string()),
-            purpose: self.purpose.clone(),
-            remittance: self.remittance.clone(),
-            information: self.information.map(|s| s.to_string()),
-        })
+    fn check_information(&self) -> Result<Option<Cow<'_, str>>, EpcError> {
+        let Some(information) = self.information.as_deref() else {
+            return Result::Ok(None);
+        };
+        let information = self.check_control_characters(Field::Information, information)?;
+        check_len(Field::Information, &information, MAX_INFORMATION_LEN)?;
+        Result::Ok((!information.is_empty()).then_some(information))
+    }
+
+    /// Reject or sanitize the control characters of a free-text field according to the
+    /// configured [`ControlCharacters`] policy
+    fn check_control_characters<'s>(
+        &self,
+        field: Field,
+        value: &'s str,
+    ) -> Result<Cow<'s, str>, EpcError> {
+        if !value.chars().any(is_control_character) {
+            return Result::Ok(Cow::Borrowed(value));
+        }
+        match self.control_characters {
+            ControlCharacters::Reject => Result::Err(EpcError::ControlCharacter { field }),
+            ControlCharacters::Sanitize => {
+                let sanitized: Vec<&str> = value
+                    .split(is_control_character)
+                    .map(str::trim)
+                    .filter(|part| !part.is_empty())
+                    .collect();
+                Result::Ok(Cow::Owned(sanitized.join(" ")))
+            }
+        }
+    }
+}
+
+/// Whether the character would break the line structure of the payload, i.e. it is a control
+/// character or one of the Unicode line and paragraph separators, which many scanners treat as
+/// line breaks
+pub(crate) fn is_control_character(c: char) -> bool {
+    c.is_control() || c == '\u{2028}' || c == '\u{2029}'
+}
+
+/// Fail with [`EpcError::FieldTooLong`] if the value has more than `max` characters
+pub(crate) fn check_len(field: Field, value: &str, max: usize) -> Result<(), EpcError> {
+    let actual = value.chars().count();
+    if actual > max {
+        Result::Err(EpcError::FieldTooLong { field, max, actual })
+    } else {
+        Result::Ok(())
     }
 }
 
@@ -487,14 +1458,14 @@ mod tests {
         assert_eq!(builder.identification, Some(Identification::Sct));
         let bic = "GENODEF1SLR";
         let builder = builder.bic(bic);
-        assert_eq!(builder.bic, Some(bic));
+        assert_eq!(builder.bic, Some(bic.parse()));
         let beneficiary = "Codeberg e.V.";
         let builder = builder.beneficiary(beneficiary);
-        assert_eq!(builder.beneficiary, Some(beneficiary));
+        assert_eq!(builder.beneficiary.as_deref(), Some(beneficiary));
         let builder = builder.iban("DE90 8306 5408 0004 1042 42");
-        assert_eq!(builder.iban, Some("DE90830654080004104242".to_string()));
+        assert_eq!(builder.iban, Some("DE90830654080004104242".parse()));
         let builder = builder.amount("999999999.99");
-        assert_eq!(builder.amount, Some("999999999.99"));
+        assert_eq!(builder.amount, Some(Amount::from_cents(99999999999)));
         let builder = builder.purpose(Purpose::Bene);
         assert_eq!(builder.purpose, Some(Purpose::Bene));
         let builder = builder.remittance(Remittance::Text(
@@ -507,12 +1478,12 @@ mod tests {
             ))
         );
         let builder = builder.information("thanks");
-        assert_eq!(builder.information, Some("thanks"));
+        assert_eq!(builder.information.as_deref(), Some("thanks"));
         let epc = builder.build();
         assert!(epc.is_ok());
         let epc = epc.unwrap();
         assert_eq!(
-            "BCD\n001\n1\nSCT\nGENODEF1SLR\nCodeberg e.V.\nDE90830654080004104242\n999999999.99\nBENE\n\ncash rules everything around me\nthanks",
+            "BCD\n001\n1\nSCT\nGENODEF1SLR\nCodeberg e.V.\nDE90830654080004104242\nEUR999999999.99\nBENE\n\ncash rules everything around me\nthanks",
             epc.to_string()
         );
     }
@@ -646,6 +1617,50 @@ mod tests {
         let builder = builder.amount("9999999990.99");
         let r = builder.build();
         assert_eq!(r, Result::Err(EpcError::InvalidAmount));
+
+        let builder = builder.amount("10.");
+        let r = builder.build();
+        assert_eq!(r, Result::Err(EpcError::InvalidAmount));
+
+        let builder = builder.amount("USD10.00");
+        let r = builder.build();
+        assert_eq!(r, Result::Err(EpcError::InvalidAmount));
+    }
+
+    #[test]
+    fn amounts_are_normalised() {
+        assert_eq!(
+            "10".parse::<Amount>().map(|a| a.to_string()),
+            Ok("EUR10.00".to_string())
+        );
+        assert_eq!(
+            "10.5".parse::<Amount>().map(|a| a.to_string()),
+            Ok("EUR10.50".to_string())
+        );
+        assert_eq!("EUR0.01".parse::<Amount>().map(|a| a.cents()), Ok(1));
+        assert_eq!(
+            Amount::from_cents(1050).map(|a| a.to_string()),
+            Ok("EUR10.50".to_string())
+        );
+        assert_eq!(Amount::from_cents(0), Err(EpcError::InvalidAmount));
+        assert_eq!(
+            Amount::from_cents(100_000_000_000),
+            Err(EpcError::InvalidAmount)
+        );
+
+        let epc = Epc::builder()
+            .version(Version::V2)
+            .character_set(CharacterSet::UTF8)
+            .identification(Identification::Sct)
+            .beneficiary("Codeberg e.V.")
+            .iban("DE90 8306 5408 0004 1042 42")
+            .amount(Amount::from_cents(1000).unwrap())
+            .build()
+            .unwrap();
+        assert_eq!(
+            "BCD\n002\n1\nSCT\n\nCodeberg e.V.\nDE90830654080004104242\nEUR10.00\n\n\n\n",
+            epc.to_string()
+        );
     }
 
     #[test]
@@ -670,6 +1685,45 @@ mod tests {
         assert_eq!(r, Result::Err(EpcError::InvalidPurpose));
     }
 
+    #[test]
+    fn parsing_purposes_works() {
+        assert_eq!("SALA".parse(), Ok(Purpose::Sala));
+        assert_eq!("IVPT".parse(), Ok(Purpose::Custom("IVPT".to_string())));
+        assert_eq!("MP2P".parse(), Ok(Purpose::Custom("MP2P".to_string())));
+        assert_eq!("ABC1".parse::<Purpose>(), Err(EpcError::InvalidPurpose));
+        assert_eq!("sala".parse::<Purpose>(), Err(EpcError::InvalidPurpose));
+
+        let ivpt = Purpose::Custom("IVPT".to_string());
+        assert_eq!(ivpt.description(), Some("Invoice payment"));
+        assert_eq!(ivpt.category(), Some(PurposeCategory::Commercial));
+        assert_eq!(Purpose::Pens.category(), Some(PurposeCategory::Pension));
+        assert_eq!(Purpose::Custom("ABCD".to_string()).description(), None);
+
+        assert!(Purpose::all().any(|p| p == Purpose::SSBE));
+        assert!(Purpose::all().all(|p| p.is_known() && p.to_string().parse() == Ok(p)));
+    }
+
+    #[test]
+    fn unknown_purpose_in_strict_mode_should_fail() {
+        let builder = Epc::builder()
+            .version(Version::V2)
+            .character_set(CharacterSet::UTF8)
+            .identification(Identification::Sct)
+            .beneficiary("Codeberg e.V.")
+            .iban("DE90 8306 5408 0004 1042 42")
+            .purpose(Purpose::Custom("ABCD".to_string()));
+        assert!(builder.build().is_ok());
+
+        let builder = builder.purpose_codes(PurposeCodes::Strict);
+        let r = builder.build();
+        assert_eq!(r, Result::Err(EpcError::InvalidPurpose));
+
+        let builder = builder.purpose(Purpose::Custom("IVPT".to_string()));
+        assert!(builder.build().is_ok());
+        let builder = builder.purpose(Purpose::Sala);
+        assert!(builder.build().is_ok());
+    }
+
     #[test]
     fn invalid_remittance_reference_should_fail() {
         let builder = Epc::builder()
@@ -704,4 +1758,670 @@ mod tests {
             epc.to_string()
         );
     }
+
+    #[test]
+    fn parsing_payloads_works() {
+        let epc = Epc::builder()
+            .version(Version::V1)
+            .character_set(CharacterSet::UTF8)
+            .identification(Identification::Sct)
+            .bic("GENODEF1SLR")
+            .beneficiary("Codeberg e.V.")
+            .iban("DE90 8306 5408 0004 1042 42")
+            .amount("10.00")
+            .purpose(Purpose::Char)
+            .remittance(Remittance::Text("for the good cause".to_string()))
+            .information("thanks")
+            .build()
+            .unwrap();
+        assert_eq!(epc.to_string().parse::<Epc>(), Ok(epc));
+
+        let epc = Epc::builder()
+            .version(Version::V2)
+            .character_set(CharacterSet::UTF8)
+            .identification(Identification::Inst)
+            .beneficiary("Codeberg e.V.")
+            .iban("DE90 8306 5408 0004 1042 42")
+            .remittance(Remittance::Reference("RF18 5390 0754 7034".to_string()))
+            .build()
+            .unwrap();
+        assert_eq!(Epc::parse(epc.to_string().as_bytes()), Ok(epc));
+    }
+
+    #[test]
+    fn round_trip_of_edge_cases_works() {
+        let builder = Epc::builder()
+            .version(Version::V2)
+            .character_set(CharacterSet::UTF8)
+            .identification(Identification::Sct)
+            .beneficiary("Codeberg e.V.")
+            .iban("DE90 8306 5408 0004 1042 42");
+
+        let epc = builder
+            .clone()
+            .purpose(Purpose::Custom("BENE".to_string()))
+            .build()
+            .unwrap();
+        assert_eq!(epc.purpose(), Some(&Purpose::Bene));
+        assert_eq!(epc.to_string().parse::<Epc>(), Ok(epc));
+
+        let epc = builder.clone().information("").build().unwrap();
+        assert_eq!(epc.information(), None);
+        assert_eq!(epc.to_string().parse::<Epc>(), Ok(epc));
+
+        let epc = builder
+            .clone()
+            .remittance(Remittance::Text(String::new()))
+            .build()
+            .unwrap();
+        assert_eq!(epc.remittance(), None);
+        assert_eq!(epc.to_string().parse::<Epc>(), Ok(epc));
+    }
+
+    #[test]
+    fn empty_beneficiary_should_fail() {
+        let epc = Epc::builder()
+            .version(Version::V2)
+            .character_set(CharacterSet::UTF8)
+            .identification(Identification::Sct)
+            .beneficiary("")
+            .iban("DE90 8306 5408 0004 1042 42")
+            .build();
+        assert_eq!(epc, Err(EpcError::MissingBeneficiary));
+    }
+
+    #[test]
+    fn parsing_payloads_with_omitted_lines_works() {
+        let epc = "BCD\r\n002\r\n1\r\nSCT\r\n\r\nCodeberg e.V.\r\nDE90830654080004104242\r\n"
+            .parse::<Epc>();
+        assert!(epc.is_ok());
+        let epc = epc.unwrap();
+        assert_eq!(
+            "BCD\n002\n1\nSCT\n\nCodeberg e.V.\nDE90830654080004104242\n\n\n\n\n",
+            epc.to_string()
+        );
+    }
+
+    #[test]
+    fn parsing_malformed_payloads_should_fail() {
+        assert_eq!("".parse::<Epc>(), Err(EpcError::MalformedPayload));
+        assert_eq!(
+            "BCD\n002\n1\nSCT\n\nCodeberg e.V.".parse::<Epc>(),
+            Err(EpcError::MalformedPayload)
+        );
+        assert_eq!(
+            "BCD\n002\n1\nSCT\n\nCodeberg e.V.\nDE90830654080004104242\n\n\nRF18539007547034\nfoo"
+                .parse::<Epc>(),
+            Err(EpcError::MalformedPayload)
+        );
+        assert_eq!(
+            "XYZ\n002\n1\nSCT\n\nCodeberg e.V.\nDE90830654080004104242".parse::<Epc>(),
+            Err(EpcError::InvalidServiceTag)
+        );
+        assert_eq!(
+            "BCD\n003\n1\nSCT\n\nCodeberg e.V.\nDE90830654080004104242".parse::<Epc>(),
+            Err(EpcError::InvalidVersion)
+        );
+        assert_eq!(
+            "BCD\n002\n9\nSCT\n\nCodeberg e.V.\nDE90830654080004104242".parse::<Epc>(),
+            Err(EpcError::InvalidCharacterSet)
+        );
+        assert_eq!(
+            "BCD\n002\n1\nSEPA\n\nCodeberg e.V.\nDE90830654080004104242".parse::<Epc>(),
+            Err(EpcError::InvalidIdentification)
+        );
+        assert_eq!(
+            "BCD\n001\n1\nSCT\n\nCodeberg e.V.\nDE90830654080004104242".parse::<Epc>(),
+            Err(EpcError::BICRequiredInConfiguredVersion)
+        );
+        assert_eq!(
+            Epc::parse(b"BCD\n002\n1\nSCT\n\nCodeberg \xff\nDE90830654080004104242"),
+            Err(EpcError::InvalidEncoding)
+        );
+    }
+
+    #[test]
+    fn encoding_in_character_sets_works() {
+        let epc = Epc::builder()
+            .version(Version::V2)
+            .character_set(CharacterSet::Iso8859_1)
+            .identification(Identification::Sct)
+            .beneficiary("Jürgen Müller")
+            .iban("DE90 8306 5408 0004 1042 42")
+            .build()
+            .unwrap();
+        let bytes = epc.to_bytes();
+        assert_eq!(
+            bytes,
+            Ok(
+                b"BCD\n002\n2\nSCT\n\nJ\xfcrgen M\xfcller\nDE90830654080004104242\n\n\n\n\n"
+                    .to_vec()
+            )
+        );
+        assert_eq!(Epc::parse(&bytes.unwrap()), Ok(epc));
+
+        let epc = Epc::builder()
+            .version(Version::V2)
+            .character_set(CharacterSet::Iso8859_1)
+            .identification(Identification::Sct)
+            .beneficiary("Codeberg e.V.")
+            .iban("DE90 8306 5408 0004 1042 42")
+            .information("5€")
+            .build();
+        assert_eq!(
+            epc,
+            Err(EpcError::UnencodableCharacter {
+                field: Field::Information,
+                character: '€'
+            })
+        );
+    }
+
+    #[test]
+    fn too_long_fields_should_fail() {
+        let builder = Epc::builder()
+            .version(Version::V2)
+            .character_set(CharacterSet::UTF8)
+            .identification(Identification::Sct)
+            .beneficiary("Codeberg e.V.")
+            .iban("DE90 8306 5408 0004 1042 42");
+        assert!(builder.build().is_ok());
+
+        let beneficiary = "ü".repeat(71);
+        let builder = builder.beneficiary(&beneficiary);
+        let r = builder.build();
+        assert_eq!(
+            r,
+            Result::Err(EpcError::FieldTooLong {
+                field: Field::Beneficiary,
+                max: 70,
+                actual: 71
+            })
+        );
+
+        let information = "i".repeat(71);
+        let builder = builder
+            .beneficiary("Codeberg e.V.")
+            .information(&information);
+        assert_eq!(
+            builder.build(),
+            Result::Err(EpcError::FieldTooLong {
+                field: Field::Information,
+                max: 70,
+                actual: 71
+            })
+        );
+
+        let builder = builder
+            .information("thanks")
+            .remittance(Remittance::Text("ü".repeat(120)));
+        assert!(builder.build().is_ok());
+        let builder = builder.remittance(Remittance::Text("ü".repeat(141)));
+        assert_eq!(
+            builder.build(),
+            Result::Err(EpcError::FieldTooLong {
+                field: Field::Remittance,
+                max: 140,
+                actual: 141
+            })
+        );
+    }
+
+    #[test]
+    fn invalid_bic_length_should_fail() {
+        let builder = Epc::builder()
+            .version(Version::V1)
+            .character_set(CharacterSet::UTF8)
+            .identification(Identification::Sct)
+            .beneficiary("Codeberg e.V.")
+            .iban("DE90 8306 5408 0004 1042 42")
+            .bic("GENODEF1");
+        assert!(builder.build().is_ok());
+        let r = builder.bic("GENODEF1SL").build();
+        assert_eq!(r, Result::Err(EpcError::InvalidBICLength { actual: 10 }));
+    }
+
+    #[test]
+    fn too_long_payload_should_fail() {
+        let beneficiary = "b".repeat(70);
+        let information = "i".repeat(70);
+        let r = Epc::builder()
+            .version(Version::V1)
+            .character_set(CharacterSet::UTF8)
+            .identification(Identification::Sct)
+            .bic("GENODEF1SLR")
+            .beneficiary(&beneficiary)
+            .iban("DE90 8306 5408 0004 1042 42")
+            .amount("999999999.99")
+            .purpose(Purpose::Char)
+            .remittance(Remittance::Text("t".repeat(140)))
+            .information(&information)
+            .build();
+        assert_eq!(
+            r,
+            Result::Err(EpcError::PayloadTooLong {
+                max: 331,
+                actual: 353
+            })
+        );
+    }
+
+    #[test]
+    fn control_characters_should_fail() {
+        let builder = Epc::builder()
+            .version(Version::V2)
+            .character_set(CharacterSet::UTF8)
+            .identification(Identification::Sct)
+            .beneficiary("Codeberg e.V.\nDE02120300000000202051")
+            .iban("DE90 8306 5408 0004 1042 42");
+        let r = builder.build();
+        assert_eq!(
+            r,
+            Result::Err(EpcError::ControlCharacter {
+                field: Field::Beneficiary
+            })
+        );
+
+        let builder = builder
+            .beneficiary("Codeberg e.V.")
+            .remittance(Remittance::Text("foo\r\nbar".to_string()));
+        let r = builder.build();
+        assert_eq!(
+            r,
+            Result::Err(EpcError::ControlCharacter {
+                field: Field::Remittance
+            })
+        );
+
+        let builder = builder
+            .remittance(Remittance::Text("foo".to_string()))
+            .information("\tthanks");
+        let r = builder.build();
+        assert_eq!(
+            r,
+            Result::Err(EpcError::ControlCharacter {
+                field: Field::Information
+            })
+        );
+    }
+
+    #[test]
+    fn control_characters_should_be_sanitized() {
+        let epc = Epc::builder()
+            .version(Version::V2)
+            .character_set(CharacterSet::UTF8)
+            .identification(Identification::Sct)
+            .control_characters(ControlCharacters::Sanitize)
+            .beneficiary("Codeberg\ne.V.")
+            .iban("DE90 8306 5408 0004 1042 42")
+            .remittance(Remittance::Text("foo \r\n bar".to_string()))
+            .information("thanks\u{7}")
+            .build();
+        assert!(epc.is_ok());
+        assert_eq!(
+            "BCD\n002\n1\nSCT\n\nCodeberg e.V.\nDE90830654080004104242\n\n\n\nfoo bar\nthanks",
+            epc.unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn unicode_line_separators_should_be_handled() {
+        let builder = Epc::builder()
+            .version(Version::V2)
+            .character_set(CharacterSet::UTF8)
+            .identification(Identification::Sct)
+            .beneficiary("Codeberg\u{2028}e.V.")
+            .iban("DE90 8306 5408 0004 1042 42")
+            .remittance(Remittance::Text("foo\u{2029}bar".to_string()));
+        assert_eq!(
+            builder.build(),
+            Err(EpcError::ControlCharacter {
+                field: Field::Beneficiary
+            })
+        );
+
+        let epc = builder
+            .control_characters(ControlCharacters::Sanitize)
+            .information("\n")
+            .build()
+            .unwrap();
+        assert_eq!(epc.beneficiary(), "Codeberg e.V.");
+        assert_eq!(
+            epc.remittance(),
+            Some(&Remittance::Text("foo bar".to_string()))
+        );
+        assert_eq!(epc.information(), None);
+        assert_eq!(epc.to_string().parse::<Epc>(), Ok(epc));
+    }
+
+    #[test]
+    fn invalid_bic_should_fail() {
+        let builder = Epc::builder()
+            .version(Version::V1)
+            .character_set(CharacterSet::UTF8)
+            .identification(Identification::Sct)
+            .beneficiary("Codeberg e.V.")
+            .iban("DE90 8306 5408 0004 1042 42")
+            .bic("HELLOXXX");
+        let r = builder.build();
+        assert_eq!(r, Result::Err(EpcError::InvalidBIC));
+    }
+
+    #[test]
+    fn bic_country_mismatch_is_a_warning() {
+        let builder = Epc::builder()
+            .version(Version::V1)
+            .character_set(CharacterSet::UTF8)
+            .identification(Identification::Sct)
+            .beneficiary("Codeberg e.V.")
+            .iban("DE90 8306 5408 0004 1042 42")
+            .bic("BNPAFRPP");
+        let r = builder.build_with_warnings();
+        assert_eq!(r.unwrap().1, vec![Warning::BICCountryMismatch]);
+
+        let builder = builder.bic("NWBKJESX").iban("GB29NWBK60161331926819");
+        let r = builder.build_with_warnings();
+        assert_eq!(r.unwrap().1, vec![]);
+    }
+
+    #[test]
+    fn validating_reports_all_errors() {
+        let builder = Epc::builder()
+            .version(Version::V1)
+            .identification(Identification::Sct)
+            .beneficiary("Codeberg e.V.\n")
+            .iban("DE90 8306 5408 0004 1042 43")
+            .amount("0.001")
+            .remittance(Remittance::Reference("RF55G72UUR".to_string()));
+        let report = builder.validate();
+        assert!(!report.is_ok());
+        assert_eq!(
+            report.errors().cloned().collect::<Vec<_>>(),
+            vec![
+                EpcError::MissingCharacterSet,
+                EpcError::BICRequiredInConfiguredVersion,
+                EpcError::ControlCharacter {
+                    field: Field::Beneficiary
+                },
+                EpcError::InvalidIBAN,
+                EpcError::InvalidAmount,
+                EpcError::InvalidRemittanceReference,
+            ]
+        );
+        assert_eq!(
+            report.field_errors(Field::Bic).collect::<Vec<_>>(),
+            vec![&EpcError::BICRequiredInConfiguredVersion]
+        );
+        assert_eq!(report.issues()[0].field, Some(Field::CharacterSet));
+        assert_eq!(builder.build(), Result::Err(EpcError::MissingCharacterSet));
+    }
+
+    #[test]
+    fn validating_valid_builders_reports_nothing() {
+        let builder = Epc::builder()
+            .version(Version::V1)
+            .character_set(CharacterSet::UTF8)
+            .identification(Identification::Sct)
+            .bic("GENODEF1SLR")
+            .beneficiary("Codeberg e.V.")
+            .iban("DE90 8306 5408 0004 1042 42");
+        let report = builder.validate();
+        assert!(report.is_ok());
+        assert_eq!(report.issues(), &[]);
+    }
+
+    #[test]
+    fn validating_reports_payload_errors() {
+        let beneficiary = "b".repeat(70);
+        let information = "i".repeat(70);
+        let report = Epc::builder()
+            .version(Version::V2)
+            .character_set(CharacterSet::UTF8)
+            .identification(Identification::Sct)
+            .beneficiary(&beneficiary)
+            .iban("DE90 8306 5408 0004 1042 42")
+            .amount("999999999.99")
+            .purpose(Purpose::Char)
+            .remittance(Remittance::Text("t".repeat(140)))
+            .information(&information)
+            .validate();
+        assert_eq!(
+            report.issues(),
+            &[ValidationIssue {
+                field: None,
+                error: EpcError::PayloadTooLong {
+                    max: 331,
+                    actual: 342
+                }
+            }]
+        );
+    }
+
+    #[test]
+    fn building_with_warnings_works() {
+        let builder = Epc::builder()
+            .version(Version::V1)
+            .character_set(CharacterSet::UTF8)
+            .identification(Identification::Sct)
+            .bic("GENODEF1SLR")
+            .beneficiary("Codeberg e.V.")
+            .iban("DE90 8306 5408 0004 1042 42")
+            .amount("10.00");
+        let r = builder.build_with_warnings();
+        assert!(r.is_ok());
+        assert_eq!(r.unwrap().1, vec![]);
+
+        let builder = builder
+            .version(Version::V2)
+            .identification(Identification::Inst)
+            .beneficiary("Jürgen Müller")
+            .remittance(Remittance::Text("RF18 5390 0754 7034".to_string()));
+        let builder = Builder {
+            amount: None,
+            ..builder
+        };
+        let r = builder.build_with_warnings();
+        assert!(r.is_ok());
+        assert_eq!(
+            r.unwrap().1,
+            vec![
+                Warning::BICInVersion2,
+                Warning::InstantWithoutAmount,
+                Warning::NonSepaCharacters {
+                    field: Field::Beneficiary
+                },
+                Warning::TextLooksLikeReference,
+            ]
+        );
+
+        let information = "i".repeat(70);
+        let builder = builder
+            .beneficiary("Codeberg e.V.")
+            .remittance(Remittance::Text("t".repeat(140)))
+            .information(&information);
+        let r = builder.build_with_warnings();
+        assert!(r.is_ok());
+        assert_eq!(r.as_ref().unwrap().0.to_bytes().unwrap().len(), 278);
+        assert_eq!(
+            r.unwrap().1,
+            vec![
+                Warning::BICInVersion2,
+                Warning::InstantWithoutAmount,
+                Warning::LargePayload { bytes: 278 },
+            ]
+        );
+    }
+
+    #[test]
+    fn accessing_fields_works() {
+        let epc = Epc::builder()
+            .version(Version::V1)
+            .character_set(CharacterSet::UTF8)
+            .identification(Identification::Sct)
+            .bic("GENODEF1SLR")
+            .beneficiary("Codeberg e.V.")
+            .iban("DE90 8306 5408 0004 1042 42")
+            .amount("10.00")
+            .purpose(Purpose::Char)
+            .remittance(Remittance::Text("for the good cause".to_string()))
+            .information("thanks")
+            .build()
+            .unwrap();
+        assert_eq!(epc.service_tag(), ServiceTag::Bcd);
+        assert_eq!(epc.version(), Version::V1);
+        assert_eq!(epc.character_set(), CharacterSet::UTF8);
+        assert_eq!(epc.identification(), Identification::Sct);
+        assert_eq!(epc.bic(), Some("GENODEF1SLR"));
+        assert_eq!(epc.beneficiary(), "Codeberg e.V.");
+        assert_eq!(epc.iban(), "DE90830654080004104242");
+        assert_eq!(epc.amount(), Amount::from_cents(1000).ok());
+        assert_eq!(epc.purpose(), Some(&Purpose::Char));
+        assert_eq!(
+            epc.remittance(),
+            Some(&Remittance::Text("for the good cause".to_string()))
+        );
+        assert_eq!(epc.information(), Some("thanks"));
+    }
+
+    #[test]
+    fn rebuilding_from_an_epc_works() {
+        let template = Epc::builder()
+            .version(Version::V2)
+            .character_set(CharacterSet::UTF8)
+            .identification(Identification::Sct)
+            .beneficiary("Codeberg e.V.")
+            .iban("DE90 8306 5408 0004 1042 42")
+            .amount("10.00")
+            .build()
+            .unwrap();
+        assert_eq!(template.to_builder().build(), Ok(template.clone()));
+
+        let epc = template
+            .to_builder()
+            .amount("25.00")
+            .remittance(Remittance::Reference("RF18 5390 0754 7034".to_string()))
+            .build()
+            .unwrap();
+        assert_eq!(epc.amount(), Amount::from_cents(2500).ok());
+        assert_eq!(epc.beneficiary(), template.beneficiary());
+        assert_eq!(
+            epc.remittance(),
+            Some(&Remittance::Reference("RF18539007547034".to_string()))
+        );
+
+        let r = template.to_builder().amount("0.00").build();
+        assert_eq!(r, Result::Err(EpcError::InvalidAmount));
+    }
+
+    #[test]
+    fn owned_builders_work() {
+        struct Template {
+            builder: Builder<'static>,
+        }
+        let beneficiary = String::from("Codeberg e.V.");
+        let template = Template {
+            builder: Epc::builder()
+                .version(Version::V2)
+                .character_set(CharacterSet::UTF8)
+                .identification(Identification::Sct)
+                .beneficiary(beneficiary)
+                .iban(String::from("DE90 8306 5408 0004 1042 42"))
+                .information(format!("invoice {}", 42)),
+        };
+        let builder = template.builder.clone();
+        let epc = std::thread::spawn(move || builder.build()).join().unwrap();
+        assert!(epc.is_ok());
+        let epc = epc.unwrap();
+        assert_eq!(epc.information(), Some("invoice 42"));
+
+        let builder: Builder<'static> = epc.clone().into_builder();
+        assert_eq!(builder.build(), Ok(epc));
+    }
+
+    #[test]
+    fn building_from_validated_values_works() {
+        let iban: Iban = "DE90 8306 5408 0004 1042 42".parse().unwrap();
+        let bic: Bic = "GENODEF1SLR".parse().unwrap();
+        let beneficiary: BeneficiaryName = "Codeberg e.V.".parse().unwrap();
+        let reference: RfReference = "RF18 5390 0754 7034".parse().unwrap();
+        let epc = Epc::builder()
+            .version(Version::V1)
+            .character_set(CharacterSet::UTF8)
+            .identification(Identification::Sct)
+            .bic(bic.clone())
+            .beneficiary_name(beneficiary)
+            .iban(iban.clone())
+            .rf_reference(reference.clone())
+            .build()
+            .unwrap();
+        assert_eq!(epc.bic(), Some(bic.as_ref()));
+        assert_eq!(epc.iban(), iban.as_ref());
+        assert_eq!(
+            epc.remittance(),
+            Some(&Remittance::Reference("RF18539007547034".to_string()))
+        );
+
+        let text = RemittanceText::try_from("for the good cause").unwrap();
+        let builder = epc.into_builder().remittance_text(text.clone());
+        assert!(builder.beneficiary_validated && builder.remittance_validated);
+        let epc = builder.build().unwrap();
+        assert_eq!(
+            epc.remittance(),
+            Some(&Remittance::Text("for the good cause".to_string()))
+        );
+
+        // The general setters check the values again
+        let builder = epc
+            .into_builder()
+            .beneficiary(String::from(BeneficiaryName::try_from("Codeberg").unwrap()))
+            .remittance(text);
+        assert!(!builder.beneficiary_validated && !builder.remittance_validated);
+        assert!(builder.build().is_ok());
+        let builder = builder.remittance_text(RemittanceText::try_from("").unwrap());
+        assert_eq!(builder.build().unwrap().remittance(), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip_works() {
+        let epc = Epc::builder()
+            .version(Version::V1)
+            .character_set(CharacterSet::UTF8)
+            .identification(Identification::Sct)
+            .bic("GENODEF1SLR")
+            .beneficiary("Codeberg e.V.")
+            .iban("DE90 8306 5408 0004 1042 42")
+            .amount("10.50")
+            .purpose(Purpose::Char)
+            .remittance(Remittance::Text("for the good cause".to_string()))
+            .build()
+            .unwrap();
+        let json = serde_json::to_string(&epc).unwrap();
+        assert_eq!(
+            json,
+            r#"{"version":"V1","character_set":"UTF8","identification":"Sct","bic":"GENODEF1SLR","beneficiary":"Codeberg e.V.","iban":"DE90830654080004104242","amount":"EUR10.50","purpose":"Char","remittance":{"Text":"for the good cause"},"information":null}"#
+        );
+        assert_eq!(serde_json::from_str::<Epc>(&json).unwrap(), epc);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserializing_invalid_epcs_should_fail() {
+        let json = r#"{"version":"V2","character_set":"UTF8","identification":"Sct","beneficiary":"Codeberg e.V.","iban":"DE90830654080004104243"}"#;
+        let r = serde_json::from_str::<Epc>(json);
+        assert_eq!(
+            r.unwrap_err().to_string(),
+            EpcError::InvalidIBAN.to_string()
+        );
+
+        let json = r#"{"version":"V1","character_set":"UTF8","identification":"Sct","beneficiary":"Codeberg e.V.","iban":"DE90830654080004104242"}"#;
+        let r = serde_json::from_str::<Epc>(json);
+        assert_eq!(
+            r.unwrap_err().to_string(),
+            EpcError::BICRequiredInConfiguredVersion.to_string()
+        );
+
+        let r = serde_json::from_str::<Amount>(r#""0.00""#);
+        assert!(r.is_err());
+    }
 }
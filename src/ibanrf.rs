@@ -1,50 +1,164 @@
-fn transform(s: &str) -> u128 {
-    let first4 = s.get(0..4).expect("expected IBAN with len >= 4");
-    let after4 = s.get(4..).expect("expected IBAN with len >= 5");
-    let switched = format!("{after4}{first4}");
-    let replaced: String = switched
-        .chars()
-        .map(|c| {
-            if c.is_numeric() {
-                c.to_string()
-            } else {
-                let v = c as u32 - 64 + 9;
-                v.to_string()
-            }
+/// Compute the ISO 7064 mod 97-10 remainder of an IBAN or RF reference
+///
+/// The first four characters are moved to the end and letters are replaced by two digits
+/// (A = 10, ..., Z = 35). The remainder is computed digit by digit, so inputs of any length are
+/// supported. Returns `None` for inputs shorter than four characters or with characters other
+/// than digits and uppercase letters.
+fn mod97(s: &str) -> Option<u32> {
+    if s.chars().count() < 4 {
+        return None;
+    }
+    s.chars()
+        .skip(4)
+        .chain(s.chars().take(4))
+        .try_fold(0, |remainder, c| match c {
+            '0'..='9' => Some((remainder * 10 + c.to_digit(10)?) % 97),
+            'A'..='Z' => Some((remainder * 100 + c.to_digit(36)?) % 97),
+            _ => None,
         })
-        .collect();
-    replaced
-        .as_str()
-        .parse()
-        .expect("expected parseable string")
+}
+
+/// Split a string into space separated groups of four characters
+fn group(s: &str) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    chars
+        .chunks(4)
+        .map(|chunk| chunk.iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join(" ")
 }
 
 /// IBAN utilities
 pub mod iban {
-    use crate::ibanrf::transform;
+    use crate::ibanrf::{group, mod97};
+    use crate::registry;
+    use std::error::Error;
+    use std::fmt::Display;
+
+    /// Reasons why an IBAN is invalid
+    #[derive(Debug, PartialEq, Clone)]
+    pub enum IbanError {
+        /// The IBAN contains other characters than uppercase letters and digits
+        InvalidCharacters,
+        /// The country isn't in the IBAN registry
+        UnknownCountry,
+        /// The length doesn't match the IBAN length of the country
+        WrongLength { expected: usize, actual: usize },
+        /// The BBAN doesn't match the BBAN structure of the country
+        BbanFormatMismatch,
+        /// The check digits don't match
+        InvalidChecksum,
+    }
+
+    impl Display for IbanError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                IbanError::InvalidCharacters => write!(f, "Invalid characters"),
+                IbanError::UnknownCountry => write!(f, "Unknown country"),
+                IbanError::WrongLength { expected, actual } => {
+                    write!(f, "Wrong length {} (expected {})", actual, expected)
+                }
+                IbanError::BbanFormatMismatch => write!(f, "BBAN doesn't match the country format"),
+                IbanError::InvalidChecksum => write!(f, "Invalid checksum"),
+            }
+        }
+    }
+
+    impl Error for IbanError {}
 
     /// Check the validity of an IBAN
     pub fn is_valid(iban: &str) -> bool {
+        validate(iban).is_ok()
+    }
+
+    /// Validate an IBAN against the length and BBAN structure registered for its country and
+    /// its check digits
+    pub fn validate(iban: &str) -> Result<(), IbanError> {
         let iban = iban.replace(" ", "");
-        iban.len() > 4
-            && iban.len() <= 34
-            && iban
-                .get(0..2)
-                .unwrap()
-                .chars()
-                .all(|c| c.is_ascii_uppercase())
-            && iban
-                .get(2..)
-                .unwrap()
-                .chars()
-                .all(|c| c.is_numeric() || c.is_ascii_uppercase())
-            && transform(iban.as_str()) % 97 == 1
+        if !iban
+            .chars()
+            .all(|c| c.is_ascii_digit() || c.is_ascii_uppercase())
+        {
+            return Err(IbanError::InvalidCharacters);
+        }
+        let format = iban
+            .get(0..2)
+            .and_then(registry::lookup)
+            .ok_or(IbanError::UnknownCountry)?;
+        if iban.len() != format.length {
+            return Err(IbanError::WrongLength {
+                expected: format.length,
+                actual: iban.len(),
+            });
+        }
+        if !format.matches(&iban[4..]) {
+            return Err(IbanError::BbanFormatMismatch);
+        }
+        if !iban[2..4].chars().all(|c| c.is_ascii_digit()) || mod97(&iban) != Some(1) {
+            return Err(IbanError::InvalidChecksum);
+        }
+        Ok(())
+    }
+
+    /// Create an IBAN from a country code and a BBAN by computing the check digits
+    pub fn from_bban(country: &str, bban: &str) -> Result<String, IbanError> {
+        let bban = electronic(bban);
+        let format = registry::lookup(country).ok_or(IbanError::UnknownCountry)?;
+        if bban.len() + 4 != format.length {
+            return Err(IbanError::WrongLength {
+                expected: format.length,
+                actual: bban.len() + 4,
+            });
+        }
+        if !format.matches(&bban) {
+            return Err(IbanError::BbanFormatMismatch);
+        }
+        let remainder =
+            mod97(format!("{country}00{bban}").as_str()).ok_or(IbanError::InvalidCharacters)?;
+        Ok(format!("{}{:02}{}", country, 98 - remainder, bban))
+    }
+
+    /// Get the electronic format of an IBAN, without spaces and in uppercase
+    pub fn electronic(iban: &str) -> String {
+        iban.chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| c.to_ascii_uppercase())
+            .collect()
+    }
+
+    /// Get the print format of an IBAN, in groups of four characters
+    pub fn format_print(iban: &str) -> String {
+        group(&electronic(iban))
+    }
+
+    /// Get the country code of a valid IBAN
+    pub fn country(iban: &str) -> Option<String> {
+        valid_electronic(iban).map(|iban| iban[0..2].to_string())
+    }
+
+    /// Get the BBAN (the part after the check digits) of a valid IBAN
+    pub fn bban(iban: &str) -> Option<String> {
+        valid_electronic(iban).map(|iban| iban[4..].to_string())
+    }
+
+    /// Get the national bank identifier of a valid IBAN, e.g. the Bankleitzahl of a German one
+    pub fn bank_identifier(iban: &str) -> Option<String> {
+        let iban = valid_electronic(iban)?;
+        let format = registry::lookup(&iban[0..2])?;
+        iban[4..]
+            .get(format.bank_identifier.clone())
+            .map(String::from)
+    }
+
+    fn valid_electronic(iban: &str) -> Option<String> {
+        let iban = electronic(iban);
+        if is_valid(&iban) { Some(iban) } else { None }
     }
 }
 
 /// RF utilities
 pub mod rf {
-    use crate::ibanrf::transform;
+    use crate::ibanrf::{group, mod97};
 
     /// Check the validity of a structured RF creditor reference
     pub fn is_valid(reference: &str) -> bool {
@@ -52,12 +166,45 @@ pub mod rf {
         reference.len() > 4
             && reference.len() <= 25
             && reference.starts_with("RF")
-            && reference
-                .get(2..)
-                .unwrap()
-                .chars()
-                .all(|c| c.is_numeric() || c.is_ascii_uppercase())
-            && transform(reference.as_str()) % 97 == 1
+            && mod97(reference.as_str()) == Some(1)
+    }
+
+    /// Generate a structured RF creditor reference from an alphanumeric body of up to 21
+    /// characters, e.g. an invoice number
+    ///
+    /// Spaces are removed and letters are uppercased. Returns `None` if the body is empty, too
+    /// long or contains other characters than letters and digits.
+    pub fn generate(body: &str) -> Option<String> {
+        let body = body.replace(" ", "").to_ascii_uppercase();
+        if body.is_empty() || body.len() > 21 {
+            return None;
+        }
+        let remainder = mod97(format!("RF00{body}").as_str())?;
+        Some(format!("RF{:02}{}", 98 - remainder, body))
+    }
+
+    /// Format a structured RF creditor reference in groups of four characters
+    pub fn format(reference: &str) -> String {
+        group(&reference.replace(" ", ""))
+    }
+
+    /// Get the body (the part after the check digits) of a valid structured RF creditor
+    /// reference
+    pub fn body(reference: &str) -> Option<String> {
+        if is_valid(reference) {
+            Some(reference.replace(" ", "")[4..].to_string())
+        } else {
+            None
+        }
+    }
+
+    /// Get the check digits of a valid structured RF creditor reference
+    pub fn check_digits(reference: &str) -> Option<u8> {
+        if is_valid(reference) {
+            reference.replace(" ", "")[2..4].parse().ok()
+        } else {
+            None
+        }
     }
 }
 
@@ -68,23 +215,149 @@ mod tests {
     use crate::ibanrf::rf;
 
     #[test]
-    fn transforming_ibans_works() {
+    fn mod97_of_ibans_works() {
         assert_eq!(
-            transform("DE68210501700012345678"),
-            210501700012345678131468
+            mod97("DE68210501700012345678"),
+            Some((210501700012345678131468u128 % 97) as u32)
         );
         assert_eq!(
-            transform("GB82WEST12345698765432"),
-            3214282912345698765432161182
+            mod97("GB82WEST12345698765432"),
+            Some((3214282912345698765432161182u128 % 97) as u32)
         )
     }
 
     #[test]
-    fn transforming_structured_references_works() {
-        assert_eq!(transform("RF45G72UUR"), 1672303027271545);
-        assert_eq!(transform("RF6518K5"), 18205271565);
-        assert_eq!(transform("RF35C4"), 124271535);
-        assert_eq!(transform("RF214377"), 4377271521);
+    fn mod97_of_structured_references_works() {
+        assert_eq!(
+            mod97("RF45G72UUR"),
+            Some((1672303027271545u128 % 97) as u32)
+        );
+        assert_eq!(mod97("RF6518K5"), Some((18205271565u128 % 97) as u32));
+        assert_eq!(mod97("RF35C4"), Some((124271535u128 % 97) as u32));
+        assert_eq!(mod97("RF214377"), Some((4377271521u128 % 97) as u32));
+    }
+
+    #[test]
+    fn mod97_of_invalid_input_is_none() {
+        assert_eq!(mod97(""), None);
+        assert_eq!(mod97("DE6"), None);
+        assert_eq!(mod97("DE68-21050170"), None);
+        assert_eq!(mod97("DE68ä21050170"), None);
+    }
+
+    #[test]
+    fn valid_ibans_of_all_registered_countries_should_validate() {
+        let ibans = [
+            "AD1200012030200359100100",
+            "AE070331234567890123456",
+            "AL47212110090000000235698741",
+            "AT611904300234573201",
+            "AZ21NABZ00000000137010001944",
+            "BA391290079401028494",
+            "BE68539007547034",
+            "BG80BNBG96611020345678",
+            "BH67BMAG00001299123456",
+            "BI4210000100010000332045181",
+            "BR1800360305000010009795493C1",
+            "BY13NBRB3600900000002Z00AB00",
+            "CH9300762011623852957",
+            "CR05015202001026284066",
+            "CY17002001280000001200527600",
+            "CZ6508000000192000145399",
+            "DE89370400440532013000",
+            "DJ2100010000000154000100186",
+            "DK5000400440116243",
+            "DO28BAGR00000001212453611324",
+            "EE382200221020145685",
+            "EG380019000500000000263180002",
+            "ES9121000418450200051332",
+            "FI2112345600000785",
+            "FK88SC123456789012",
+            "FO6264600001631634",
+            "FR1420041010050500013M02606",
+            "GB29NWBK60161331926819",
+            "GE29NB0000000101904917",
+            "GI75NWBK000000007099453",
+            "GL8964710001000206",
+            "GR1601101250000000012300695",
+            "GT82TRAJ01020000001210029690",
+            "HN88CABF00000000000250005469",
+            "HR1210010051863000160",
+            "HU42117730161111101800000000",
+            "IE29AIBK93115212345678",
+            "IL620108000000099999999",
+            "IQ98NBIQ850123456789012",
+            "IS140159260076545510730339",
+            "IT60X0542811101000000123456",
+            "JO94CBJO0010000000000131000302",
+            "KW81CBKU0000000000001234560101",
+            "KZ86125KZT5004100100",
+            "LB62099900000001001901229114",
+            "LC55HEMM000100010012001200023015",
+            "LI21088100002324013AA",
+            "LT121000011101001000",
+            "LU280019400644750000",
+            "LV80BANK0000435195001",
+            "LY83002048000020100120361",
+            "MC5811222000010123456789030",
+            "MD24AG000225100013104168",
+            "ME25505000012345678951",
+            "MK07250120000058984",
+            "MN121234123456789123",
+            "MR1300020001010000123456753",
+            "MT84MALT011000012345MTLCAST001S",
+            "MU17BOMM0101101030300200000MUR",
+            "NI45BAPR00000013000003558124",
+            "NL91ABNA0417164300",
+            "NO9386011117947",
+            "OM810180000001299123456",
+            "PK36SCBL0000001123456702",
+            "PL61109010140000071219812874",
+            "PS92PALS000000000400123456702",
+            "PT50000201231234567890154",
+            "QA58DOHB00001234567890ABCDEFG",
+            "RO49AAAA1B31007593840000",
+            "RS35260005601001611379",
+            "RU0304452522540817810538091310419",
+            "SA0380000000608010167519",
+            "SC18SSCB11010000000000001497USD",
+            "SD2129010501234001",
+            "SE4550000000058398257466",
+            "SI56263300012039086",
+            "SK3112000000198742637541",
+            "SM86U0322509800000000270100",
+            "SO211000001001000100141",
+            "ST68000100010051845310112",
+            "SV62CENR00000000000000700025",
+            "TL380080012345678910157",
+            "TN5910006035183598478831",
+            "TR330006100519786457841326",
+            "UA213223130000026007233566001",
+            "VA59001123000012345678",
+            "VG96VPVG0000012345678901",
+            "XK051212012345678906",
+            "YE15CBYE0001018861234567891234",
+        ];
+        for i in ibans {
+            assert_eq!(iban::validate(i), Ok(()), "{}", i);
+        }
+    }
+
+    #[test]
+    fn validating_arbitrary_input_should_not_panic() {
+        for input in [
+            "ä",
+            "äö",
+            "DÄ90",
+            "ÄÖ90830654080004104242",
+            "€€€€€",
+            "RF",
+            "RFÄÖ",
+            "RF€5",
+        ] {
+            assert!(!iban::is_valid(input));
+            assert!(!rf::is_valid(input));
+        }
     }
 
     #[test]
@@ -93,12 +366,139 @@ mod tests {
         assert!(!iban::is_valid("DE90830654080004104243"));
     }
 
+    #[test]
+    fn validating_ibans_reports_the_reason() {
+        assert_eq!(iban::validate("DE90 8306 5408 0004 1042 42"), Ok(()));
+        assert_eq!(iban::validate("GB82WEST12345698765432"), Ok(()));
+        assert_eq!(iban::validate("NO9386011117947"), Ok(()));
+        assert_eq!(
+            iban::validate("de90830654080004104242"),
+            Err(iban::IbanError::InvalidCharacters)
+        );
+        assert_eq!(
+            iban::validate("XX90830654080004104242"),
+            Err(iban::IbanError::UnknownCountry)
+        );
+        assert_eq!(
+            iban::validate("DE9083065408000410424"),
+            Err(iban::IbanError::WrongLength {
+                expected: 22,
+                actual: 21
+            })
+        );
+        assert_eq!(
+            iban::validate("DE90830654080004104A42"),
+            Err(iban::IbanError::BbanFormatMismatch)
+        );
+        assert_eq!(
+            iban::validate("DE91830654080004104242"),
+            Err(iban::IbanError::InvalidChecksum)
+        );
+    }
+
+    #[test]
+    fn creating_ibans_from_bbans_works() {
+        assert_eq!(
+            iban::from_bban("DE", "8306 5408 0004 1042 42"),
+            Ok("DE90830654080004104242".to_string())
+        );
+        assert_eq!(
+            iban::from_bban("GB", "NWBK60161331926819"),
+            Ok("GB29NWBK60161331926819".to_string())
+        );
+        assert_eq!(
+            iban::from_bban("XX", "830654080004104242"),
+            Err(iban::IbanError::UnknownCountry)
+        );
+        assert_eq!(
+            iban::from_bban("DE", "83065408000410424"),
+            Err(iban::IbanError::WrongLength {
+                expected: 22,
+                actual: 21
+            })
+        );
+        assert_eq!(
+            iban::from_bban("DE", "8306540800041042AB"),
+            Err(iban::IbanError::BbanFormatMismatch)
+        );
+    }
+
+    #[test]
+    fn formatting_ibans_works() {
+        assert_eq!(
+            iban::electronic(" de90 8306 5408 0004 1042 42"),
+            "DE90830654080004104242"
+        );
+        assert_eq!(
+            iban::format_print("DE90830654080004104242"),
+            "DE90 8306 5408 0004 1042 42"
+        );
+        assert_eq!(
+            iban::format_print("be68 5390 0754 7034"),
+            "BE68 5390 0754 7034"
+        );
+    }
+
+    #[test]
+    fn accessing_parts_of_ibans_works() {
+        let i = "DE90 8306 5408 0004 1042 42";
+        assert_eq!(iban::country(i), Some("DE".to_string()));
+        assert_eq!(iban::bban(i), Some("830654080004104242".to_string()));
+        assert_eq!(iban::bank_identifier(i), Some("83065408".to_string()));
+        assert_eq!(
+            iban::bank_identifier("IT60X0542811101000000123456"),
+            Some("05428".to_string())
+        );
+        assert_eq!(
+            iban::bank_identifier("GB29NWBK60161331926819"),
+            Some("NWBK".to_string())
+        );
+        assert_eq!(iban::country("DE90830654080004104243"), None);
+        assert_eq!(iban::bank_identifier("DE90830654080004104243"), None);
+    }
+
     #[test]
     fn invalid_structured_references_should_fail() {
         assert!(!rf::is_valid(""));
         assert!(!rf::is_valid("RF55G72UUR"));
     }
 
+    #[test]
+    fn generating_structured_references_works() {
+        assert_eq!(rf::generate("G72UUR"), Some("RF45G72UUR".to_string()));
+        assert_eq!(
+            rf::generate("5390 0754 7034"),
+            Some("RF18539007547034".to_string())
+        );
+        assert_eq!(rf::generate("c4"), Some("RF51C4".to_string()));
+        assert_eq!(rf::generate("7"), Some("RF097".to_string()));
+        for body in ["1", "INV2025001", "123456789012345678901"] {
+            assert!(rf::is_valid(&rf::generate(body).unwrap()));
+        }
+        assert_eq!(rf::generate(""), None);
+        assert_eq!(rf::generate("INV-2025-001"), None);
+        assert_eq!(rf::generate("1234567890123456789012"), None);
+    }
+
+    #[test]
+    fn formatting_structured_references_works() {
+        assert_eq!(rf::format("RF18539007547034"), "RF18 5390 0754 7034");
+        assert_eq!(rf::format("RF18 5390 075 47034"), "RF18 5390 0754 7034");
+        assert_eq!(rf::format("RF45G72UUR"), "RF45 G72U UR");
+    }
+
+    #[test]
+    fn accessing_parts_of_structured_references_works() {
+        assert_eq!(
+            rf::body("RF18 5390 0754 7034"),
+            Some("539007547034".to_string())
+        );
+        assert_eq!(rf::check_digits("RF18 5390 0754 7034"), Some(18));
+        assert_eq!(rf::check_digits("RF097"), Some(9));
+        assert_eq!(rf::body("RF55G72UUR"), None);
+        assert_eq!(rf::check_digits("RF55G72UUR"), None);
+    }
+
     #[test]
     fn valid_structured_references_should_validate() {
         assert!(rf::is_valid("RF18 5390 0754 7034"));
@@ -29,8 +29,41 @@
 //! image.save("./examples_basic_usage_qrcode.png").unwrap();
 //! ```
 
+pub mod bic;
+mod charset;
+#[cfg(feature = "decode")]
+pub mod decode;
 mod ibanrf;
+#[cfg(feature = "pdf")]
+pub mod pdf;
+#[cfg(feature = "png")]
+pub mod png;
+mod purpose;
+pub use purpose::PurposeCategory;
+#[cfg(feature = "qr")]
+pub mod qr;
+mod registry;
+#[cfg(feature = "svg")]
+pub mod svg;
+#[cfg(feature = "terminal")]
+pub mod terminal;
+pub mod typed;
+mod types;
 pub use ibanrf::iban;
 pub use ibanrf::rf;
+pub use types::*;
 mod epcgen;
 pub use epcgen::*;
+
+/// The EPC of the module examples, shared by the tests of the renderers and the decoder
+#[cfg(all(test, any(feature = "decode", feature = "png", feature = "svg")))]
+pub(crate) fn test_epc() -> Epc {
+    Epc::builder()
+        .version(Version::V2)
+        .character_set(CharacterSet::UTF8)
+        .identification(Identification::Sct)
+        .beneficiary("Codeberg e.V.")
+        .iban("DE90 8306 5408 0004 1042 42")
+        .build()
+        .unwrap()
+}
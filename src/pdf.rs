@@ -0,0 +1,676 @@
+//! PDF payment slips with the QR code and the payment details of an EPC
+//!
+//! The documents only use the standard Helvetica fonts, which every PDF viewer provides, so
+//! no fonts are embedded. Characters outside of the Windows-1252 character set of these fonts
+//! are replaced with `?`.
+//!
+//! # Examples
+//! ```
+//! use epcgen::pdf::{PdfLayout, PdfOptions};
+//! use epcgen::{CharacterSet, Epc, Identification, Version};
+//!
+//! let epc = Epc::builder()
+//!     .version(Version::V2)
+//!     .character_set(CharacterSet::UTF8)
+//!     .identification(Identification::Sct)
+//!     .beneficiary("Codeberg e.V.")
+//!     .iban("DE90 8306 5408 0004 1042 42")
+//!     .amount("10.00")
+//!     .build()
+//!     .unwrap();
+//! let options = PdfOptions {
+//!     layout: PdfLayout::Block,
+//!     ..PdfOptions::default()
+//! };
+//! let pdf = epc.render_pdf(&options).unwrap();
+//! assert!(pdf.starts_with(b"%PDF-1.4"));
+//! ```
+
+use crate::ibanrf::{iban, rf};
+use crate::qr::{MIN_MODULE_SIZE_MM, QUIET_ZONE, QrCode};
+use crate::{Epc, EpcError, Remittance};
+use std::error::Error;
+use std::fmt::Display;
+
+/// Points per millimetre
+const PT_PER_MM: f64 = 72.0 / 25.4;
+
+/// Width and height of an A6 page in millimetres
+const A6_MM: (f64, f64) = (105.0, 148.0);
+
+/// Margin of a slip in millimetres
+const SLIP_MARGIN: f64 = 10.0;
+/// Height of the caption above the code on a slip in millimetres
+const SLIP_CAPTION: f64 = 10.0;
+/// Space between the code and the details on a slip in millimetres
+const SLIP_GAP: f64 = 6.0;
+/// Margin of a block and space between its code and details in millimetres
+const BLOCK_MARGIN: f64 = 5.0;
+/// Narrowest details next to the code of a block in millimetres
+const BLOCK_MIN_DETAILS: f64 = 40.0;
+
+/// Widths of the printable ASCII characters in Helvetica, in thousandths of the font size
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556, 556,
+    556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, 1015, 667, 667, 722, 722, 667,
+    611, 778, 722, 278, 500, 667, 556, 833, 722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667,
+    667, 611, 278, 278, 278, 469, 556, 333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500,
+    222, 833, 556, 556, 556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+/// Characters of Windows-1252 at 0x80-0x9F, 0 marks unassigned bytes
+const WINDOWS_1252: [u16; 32] = [
+    0x20AC, 0, 0x201A, 0x0192, 0x201E, 0x2026, 0x2020, 0x2021, 0x02C6, 0x2030, 0x0160, 0x2039,
+    0x0152, 0, 0x017D, 0, 0, 0x2018, 0x2019, 0x201C, 0x201D, 0x2022, 0x2013, 0x2014, 0x02DC,
+    0x2122, 0x0161, 0x203A, 0x0153, 0, 0x017E, 0x0178,
+];
+
+/// Arrangement of the payment details
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum PdfLayout {
+    /// A standalone A6 page with the caption on top, the QR code and the details below it
+    #[default]
+    Slip,
+    /// A page just large enough for the QR code and the details next to it, for embedding
+    /// into other documents like invoices
+    Block,
+}
+
+/// Options for rendering an EPC as PDF
+#[derive(Debug, PartialEq, Clone)]
+pub struct PdfOptions {
+    /// Arrangement of the payment details
+    pub layout: PdfLayout,
+    /// Width of the QR code including the quiet zone in millimetres
+    pub code_size_mm: f64,
+    /// Caption asking to scan the code
+    pub caption: String,
+}
+
+impl Default for PdfOptions {
+    fn default() -> Self {
+        PdfOptions {
+            layout: PdfLayout::default(),
+            code_size_mm: 40.0,
+            caption: "Scan to pay".to_string(),
+        }
+    }
+}
+
+/// Possible errors when rendering an EPC as PDF
+#[derive(Debug, PartialEq, Clone)]
+pub enum PdfError {
+    /// The EPC can't be encoded
+    Epc(EpcError),
+    /// The code size isn't a positive number of millimetres
+    InvalidSize { size_mm: f64 },
+    /// The modules would be smaller than [`MIN_MODULE_SIZE_MM`] at the requested code size
+    ModuleTooSmall { actual_mm: f64, min_mm: f64 },
+    /// The code doesn't fit onto the page of the layout together with the details
+    CodeTooLarge { size_mm: f64, max_mm: f64 },
+}
+
+impl Display for PdfError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PdfError::Epc(e) => write!(f, "{}", e),
+            PdfError::InvalidSize { size_mm } => {
+                write!(f, "Invalid size {} mm, must be a positive number", size_mm)
+            }
+            PdfError::ModuleTooSmall { actual_mm, min_mm } => write!(
+                f,
+                "Modules of {:.3} mm are too small to scan (min {} mm)",
+                actual_mm, min_mm
+            ),
+            PdfError::CodeTooLarge { size_mm, max_mm } => write!(
+                f,
+                "Code of {} mm doesn't fit onto the page (max {:.1} mm)",
+                size_mm, max_mm
+            ),
+        }
+    }
+}
+
+impl Error for PdfError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PdfError::Epc(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<EpcError> for PdfError {
+    fn from(e: EpcError) -> Self {
+        PdfError::Epc(e)
+    }
+}
+
+impl Epc {
+    /// Render a PDF document with the QR code and the payment details of the EPC
+    pub fn render_pdf(&self, options: &PdfOptions) -> Result<Vec<u8>, PdfError> {
+        let size_mm = options.code_size_mm;
+        if !(size_mm.is_finite() && size_mm > 0.0) {
+            return Err(PdfError::InvalidSize { size_mm });
+        }
+        let code = self.to_qr_code()?;
+        let module_mm = size_mm / (code.size() + 2 * QUIET_ZONE) as f64;
+        if module_mm < MIN_MODULE_SIZE_MM {
+            return Err(PdfError::ModuleTooSmall {
+                actual_mm: module_mm,
+                min_mm: MIN_MODULE_SIZE_MM,
+            });
+        }
+        let details = details(self);
+        let max_mm = max_code_size(options.layout, &details);
+        if size_mm > max_mm {
+            return Err(PdfError::CodeTooLarge { size_mm, max_mm });
+        }
+
+        let mut page = Page::default();
+        let size = match options.layout {
+            PdfLayout::Slip => slip(&mut page, &code, &details, options),
+            PdfLayout::Block => block(&mut page, &code, &details, options),
+        };
+        Ok(document(size, &page.content))
+    }
+}
+
+/// The labelled payment details shown next to the code
+fn details(epc: &Epc) -> Vec<(&'static str, String)> {
+    let mut details = vec![
+        ("Beneficiary", epc.beneficiary().to_string()),
+        ("IBAN", iban::format_print(epc.iban())),
+    ];
+    if let Some(bic) = epc.bic() {
+        details.push(("BIC", bic.to_string()));
+    }
+    if let Some(amount) = epc.amount() {
+        let cents = amount.cents();
+        details.push(("Amount", format!("EUR {}.{:02}", cents / 100, cents % 100)));
+    }
+    match epc.remittance() {
+        Some(Remittance::Reference(r)) => details.push(("Reference", rf::format(r))),
+        Some(Remittance::Text(t)) => details.push(("Reference", t.clone())),
+        None => (),
+    }
+    details
+}
+
+/// The largest code size in millimetres leaving room for the details in the layout
+fn max_code_size(layout: PdfLayout, details: &[(&str, String)]) -> f64 {
+    let (width, height) = A6_MM;
+    match layout {
+        PdfLayout::Slip => {
+            let details_width = width - 2.0 * SLIP_MARGIN;
+            let details_height = details_height(details, details_width);
+            let free_height = height - 2.0 * SLIP_MARGIN - SLIP_CAPTION - SLIP_GAP - details_height;
+            details_width.min(free_height)
+        }
+        PdfLayout::Block => width - 3.0 * BLOCK_MARGIN - BLOCK_MIN_DETAILS,
+    }
+}
+
+/// Lay out an A6 slip and return its size in millimetres
+fn slip(
+    page: &mut Page,
+    code: &QrCode,
+    details: &[(&str, String)],
+    options: &PdfOptions,
+) -> (f64, f64) {
+    let (width, height) = A6_MM;
+    let margin = SLIP_MARGIN;
+    page.cut_line(width, height);
+    page.text_centered(
+        width / 2.0,
+        height - margin - SLIP_CAPTION / 2.0,
+        14.0,
+        true,
+        &options.caption,
+    );
+    let code_top = height - margin - SLIP_CAPTION;
+    page.code(
+        code,
+        (width - options.code_size_mm) / 2.0,
+        code_top,
+        options.code_size_mm,
+    );
+    page.details(
+        details,
+        margin,
+        code_top - options.code_size_mm - SLIP_GAP,
+        width - 2.0 * margin,
+    );
+    (width, height)
+}
+
+/// Lay out a block with the code on the left and the details on the right and return its
+/// size in millimetres
+fn block(
+    page: &mut Page,
+    code: &QrCode,
+    details: &[(&str, String)],
+    options: &PdfOptions,
+) -> (f64, f64) {
+    let width = A6_MM.0;
+    let margin = BLOCK_MARGIN;
+    let details_x = margin + options.code_size_mm + margin;
+    let details_width = width - details_x - margin;
+    let details_height = details_height(details, details_width);
+    let height = (options.code_size_mm + 6.0).max(details_height) + 2.0 * margin;
+    let top = height - margin;
+
+    page.code(code, margin, top, options.code_size_mm);
+    page.text_centered(
+        margin + options.code_size_mm / 2.0,
+        top - options.code_size_mm - 4.0,
+        9.0,
+        true,
+        &options.caption,
+    );
+    page.details(details, details_x, top, details_width);
+    (width, height)
+}
+
+const LABEL_SIZE: f64 = 7.0;
+const VALUE_SIZE: f64 = 10.0;
+/// Distance of two lines relative to the font size
+const LINE_HEIGHT: f64 = 1.25;
+/// Space between two details in millimetres
+const DETAIL_GAP: f64 = 2.0;
+
+/// Height of the details in millimetres
+fn details_height(details: &[(&str, String)], width: f64) -> f64 {
+    details
+        .iter()
+        .map(|(_, value)| {
+            let lines = wrap(value, VALUE_SIZE, width).len() as f64;
+            (LABEL_SIZE + lines * VALUE_SIZE) * LINE_HEIGHT / PT_PER_MM + DETAIL_GAP
+        })
+        .sum()
+}
+
+/// Width of a text in millimetres
+fn text_width(text: &str, font_size: f64) -> f64 {
+    let units: u32 = text
+        .chars()
+        .map(|c| match c {
+            ' '..='~' => HELVETICA_WIDTHS[c as usize - 32] as u32,
+            _ => 556,
+        })
+        .sum();
+    units as f64 * font_size / 1000.0 / PT_PER_MM
+}
+
+/// Break a text into lines fitting into the width, at spaces where possible
+fn wrap(text: &str, font_size: f64, width: f64) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split(' ') {
+        let candidate = if line.is_empty() {
+            word.to_string()
+        } else {
+            format!("{} {}", line, word)
+        };
+        if text_width(&candidate, font_size) <= width {
+            line = candidate;
+            continue;
+        }
+        if !line.is_empty() {
+            lines.push(std::mem::take(&mut line));
+        }
+        // Break words which don't fit into a line on their own
+        for c in word.chars() {
+            line.push(c);
+            if text_width(&line, font_size) > width {
+                line.pop();
+                lines.push(std::mem::take(&mut line));
+                line.push(c);
+            }
+        }
+    }
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// Content stream of a page, using millimetres with the origin in the lower left corner
+#[derive(Default)]
+struct Page {
+    content: Vec<u8>,
+}
+
+impl Page {
+    fn op(&mut self, op: &str) {
+        self.content.extend(op.as_bytes());
+        self.content.push(b'\n');
+    }
+
+    /// Draw a dashed line along the edges of the page for cutting it out
+    fn cut_line(&mut self, width: f64, height: f64) {
+        let inset = 0.5;
+        self.op(&format!(
+            "q 0.5 G 0.5 w [3 3] 0 d {:.2} {:.2} {:.2} {:.2} re S Q",
+            inset * PT_PER_MM,
+            inset * PT_PER_MM,
+            (width - 2.0 * inset) * PT_PER_MM,
+            (height - 2.0 * inset) * PT_PER_MM
+        ));
+    }
+
+    /// Draw the code with its quiet zone, `top` being its upper edge
+    fn code(&mut self, code: &QrCode, x: f64, top: f64, size: f64) {
+        let module = size / (code.size() + 2 * QUIET_ZONE) as f64 * PT_PER_MM;
+        let left = x * PT_PER_MM + QUIET_ZONE as f64 * module;
+        let top = top * PT_PER_MM - QUIET_ZONE as f64 * module;
+        self.op("0 g");
+        for y in 0..code.size() {
+            for x in 0..code.size() {
+                if code.is_dark(x, y) {
+                    self.op(&format!(
+                        "{:.3} {:.3} {:.3} {:.3} re",
+                        left + x as f64 * module,
+                        top - (y + 1) as f64 * module,
+                        module,
+                        module
+                    ));
+                }
+            }
+        }
+        self.op("f");
+    }
+
+    /// Write a line of text, `baseline` being its baseline
+    fn text(&mut self, x: f64, baseline: f64, font_size: f64, bold: bool, text: &str) {
+        self.op(&format!(
+            "BT /{} {} Tf {:.2} {:.2} Td",
+            if bold { "F2" } else { "F1" },
+            font_size,
+            x * PT_PER_MM,
+            baseline * PT_PER_MM
+        ));
+        self.content.push(b'(');
+        for c in text.chars() {
+            match c {
+                '(' | ')' | '\\' => self.content.extend([b'\\', c as u8]),
+                ' '..='~' | '\u{a0}'..='\u{ff}' => self.content.push(c as u32 as u8),
+                _ => self.content.push(
+                    WINDOWS_1252
+                        .iter()
+                        .position(|&t| t != 0 && u32::from(t) == c as u32)
+                        .map_or(b'?', |i| 0x80 + i as u8),
+                ),
+            }
+        }
+        self.op(") Tj ET");
+    }
+
+    /// Write a line of text centred around `x`
+    fn text_centered(&mut self, x: f64, baseline: f64, font_size: f64, bold: bool, text: &str) {
+        // The widths of Helvetica are a close enough estimate for the bold variant
+        let width = text_width(text, font_size);
+        self.text(x - width / 2.0, baseline, font_size, bold, text);
+    }
+
+    /// Write the labelled details below `top`, wrapping the values to the width
+    fn details(&mut self, details: &[(&str, String)], x: f64, top: f64, width: f64) {
+        let mut y = top;
+        for (label, value) in details {
+            y -= LABEL_SIZE * LINE_HEIGHT / PT_PER_MM;
+            self.text(x, y, LABEL_SIZE, true, label);
+            for line in wrap(value, VALUE_SIZE, width) {
+                y -= VALUE_SIZE * LINE_HEIGHT / PT_PER_MM;
+                self.text(x, y, VALUE_SIZE, false, &line);
+            }
+            y -= DETAIL_GAP;
+        }
+    }
+}
+
+/// Assemble a PDF document with one page of the given size in millimetres
+fn document((width, height): (f64, f64), content: &[u8]) -> Vec<u8> {
+    let objects: [Vec<u8>; 6] = [
+        b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+        b"<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_vec(),
+        format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {:.2} {:.2}] \
+             /Resources << /Font << /F1 5 0 R /F2 6 0 R >> >> /Contents 4 0 R >>",
+            width * PT_PER_MM,
+            height * PT_PER_MM
+        )
+        .into_bytes(),
+        [
+            format!("<< /Length {} >>\nstream\n", content.len()).as_bytes(),
+            content,
+            b"endstream",
+        ]
+        .concat(),
+        font("Helvetica"),
+        font("Helvetica-Bold"),
+    ];
+
+    let mut pdf = b"%PDF-1.4\n%\xe2\xe3\xcf\xd3\n".to_vec();
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend(format!("{} 0 obj\n", i + 1).as_bytes());
+        pdf.extend(object);
+        pdf.extend(b"\nendobj\n");
+    }
+    let xref = pdf.len();
+    pdf.extend(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes());
+    for offset in offsets {
+        pdf.extend(format!("{:010} 00000 n \n", offset).as_bytes());
+    }
+    pdf.extend(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            xref
+        )
+        .as_bytes(),
+    );
+    pdf
+}
+
+fn font(name: &str) -> Vec<u8> {
+    format!(
+        "<< /Type /Font /Subtype /Type1 /BaseFont /{} /Encoding /WinAnsiEncoding >>",
+        name
+    )
+    .into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CharacterSet, Identification, Version};
+
+    fn epc() -> Epc {
+        Epc::builder()
+            .version(Version::V1)
+            .character_set(CharacterSet::UTF8)
+            .identification(Identification::Sct)
+            .bic("GENODEF1SLR")
+            .beneficiary("Codeberg e.V.")
+            .iban("DE90830654080004104242")
+            .amount("1234.5")
+            .remittance(Remittance::Reference("RF18539007547034".to_string()))
+            .build()
+            .unwrap()
+    }
+
+    fn contains(haystack: &[u8], needle: &str) -> bool {
+        haystack
+            .windows(needle.len())
+            .any(|w| w == needle.as_bytes())
+    }
+
+    #[test]
+    fn rendering_slip_works() {
+        let pdf = epc().render_pdf(&PdfOptions::default()).unwrap();
+        assert!(pdf.starts_with(b"%PDF-1.4"));
+        assert!(pdf.ends_with(b"%%EOF\n"));
+        assert!(contains(&pdf, "/MediaBox [0 0 297.64 419.53]"));
+        assert!(contains(&pdf, "(Scan to pay) Tj"));
+        assert!(contains(&pdf, "(Codeberg e.V.) Tj"));
+        assert!(contains(&pdf, "(DE90 8306 5408 0004 1042 42) Tj"));
+        assert!(contains(&pdf, "(GENODEF1SLR) Tj"));
+        assert!(contains(&pdf, "(EUR 1234.50) Tj"));
+        assert!(contains(&pdf, "(RF18 5390 0754 7034) Tj"));
+    }
+
+    #[test]
+    fn cross_reference_table_is_consistent() {
+        let pdf = epc().render_pdf(&PdfOptions::default()).unwrap();
+        let text = String::from_utf8_lossy(&pdf);
+        let xref: usize = text
+            .rsplit("startxref\n")
+            .next()
+            .and_then(|s| s.lines().next())
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!(pdf[xref..].starts_with(b"xref"));
+        let table = &text[text.find("xref\n0 7\n").unwrap()..];
+        for (i, line) in table.lines().skip(3).take(6).enumerate() {
+            let offset: usize = line[..10].parse().unwrap();
+            assert!(pdf[offset..].starts_with(format!("{} 0 obj", i + 1).as_bytes()));
+        }
+    }
+
+    #[test]
+    fn rendering_block_works() {
+        let options = PdfOptions {
+            layout: PdfLayout::Block,
+            caption: "Zahlen mit Code".to_string(),
+            ..PdfOptions::default()
+        };
+        let pdf = epc().render_pdf(&options).unwrap();
+        // Five details of one line each are taller than the code with its caption
+        assert!(contains(&pdf, "/MediaBox [0 0 297.64 162.94]"));
+        assert!(contains(&pdf, "(Zahlen mit Code) Tj"));
+        assert!(!contains(&pdf, "[3 3] 0 d"));
+    }
+
+    #[test]
+    fn invalid_code_sizes_should_fail() {
+        let epc = epc();
+        for code_size_mm in [0.0, -5.0, f64::INFINITY] {
+            let options = PdfOptions {
+                code_size_mm,
+                ..PdfOptions::default()
+            };
+            assert_eq!(
+                epc.render_pdf(&options),
+                Err(PdfError::InvalidSize {
+                    size_mm: code_size_mm
+                })
+            );
+        }
+        let options = PdfOptions {
+            code_size_mm: f64::NAN,
+            ..PdfOptions::default()
+        };
+        assert!(matches!(
+            epc.render_pdf(&options),
+            Err(PdfError::InvalidSize { .. })
+        ));
+
+        let options = PdfOptions {
+            code_size_mm: 2.0,
+            ..PdfOptions::default()
+        };
+        assert!(matches!(
+            epc.render_pdf(&options),
+            Err(PdfError::ModuleTooSmall { .. })
+        ));
+    }
+
+    #[test]
+    fn too_large_codes_should_fail() {
+        let epc = epc();
+        let options = PdfOptions {
+            code_size_mm: 300.0,
+            ..PdfOptions::default()
+        };
+        assert!(matches!(
+            epc.render_pdf(&options),
+            Err(PdfError::CodeTooLarge { .. })
+        ));
+        // Five details of one line leave 64.5 mm for the code on a slip
+        let options = PdfOptions {
+            code_size_mm: 64.0,
+            ..PdfOptions::default()
+        };
+        assert!(epc.render_pdf(&options).is_ok());
+        let options = PdfOptions {
+            code_size_mm: 65.0,
+            ..PdfOptions::default()
+        };
+        assert!(matches!(
+            epc.render_pdf(&options),
+            Err(PdfError::CodeTooLarge { .. })
+        ));
+
+        let options = PdfOptions {
+            layout: PdfLayout::Block,
+            code_size_mm: 120.0,
+            ..PdfOptions::default()
+        };
+        assert_eq!(
+            epc.render_pdf(&options),
+            Err(PdfError::CodeTooLarge {
+                size_mm: 120.0,
+                max_mm: 50.0
+            })
+        );
+        let options = PdfOptions {
+            layout: PdfLayout::Block,
+            code_size_mm: 50.0,
+            ..PdfOptions::default()
+        };
+        assert!(epc.render_pdf(&options).is_ok());
+    }
+
+    #[test]
+    fn wrapping_text_works() {
+        assert_eq!(wrap("", 10.0, 50.0), vec![""]);
+        assert_eq!(
+            wrap("for the good cause", 10.0, 50.0),
+            vec!["for the good cause"]
+        );
+        assert_eq!(
+            wrap("for the good cause", 10.0, 15.0),
+            vec!["for the", "good", "cause"]
+        );
+        let lines = wrap(&"x".repeat(60), 10.0, 20.0);
+        assert!(lines.len() > 1);
+        assert!(lines.iter().all(|l| text_width(l, 10.0) <= 20.0));
+        assert_eq!(lines.concat(), "x".repeat(60));
+    }
+
+    #[test]
+    fn special_characters_are_escaped() {
+        let mut page = Page::default();
+        page.text(0.0, 0.0, 10.0, false, "(a\\b) 5€ Ä 日");
+        assert!(contains(&page.content, "(\\(a\\\\b\\) 5"));
+        assert!(page.content.ends_with(b" \xc4 ?) Tj ET\n"));
+        assert!(page.content.contains(&0x80));
+    }
+
+    #[test]
+    fn windows_1252_characters_are_encoded() {
+        let mut page = Page::default();
+        page.text(
+            0.0,
+            0.0,
+            10.0,
+            false,
+            "Škoda Œuvre Ÿ šžœŽ ‚„‘’“” –— …•™ \u{81}",
+        );
+        let encoded: &[u8] = b"(\x8akoda \x8cuvre \x9f \x9a\x9e\x9c\x8e \x82\x84\x91\x92\x93\x94 \x96\x97 \x85\x95\x99 ?) Tj ET\n";
+        assert!(page.content.ends_with(encoded));
+    }
+}
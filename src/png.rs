@@ -0,0 +1,298 @@
+//! PNG rendering of EPC QR codes in print quality
+//!
+//! The module size is chosen for the target resolution, so that every module covers a whole
+//! number of pixels and is at least [`MIN_MODULE_SIZE_MM`] wide when printed.
+//!
+//! # Examples
+//! ```
+//! use epcgen::png::PngOptions;
+//! use epcgen::{CharacterSet, Epc, Identification, Version};
+//!
+//! let epc = Epc::builder()
+//!     .version(Version::V2)
+//!     .character_set(CharacterSet::UTF8)
+//!     .identification(Identification::Sct)
+//!     .beneficiary("Codeberg e.V.")
+//!     .iban("DE90 8306 5408 0004 1042 42")
+//!     .build()
+//!     .unwrap();
+//! let options = PngOptions {
+//!     dpi: 600,
+//!     size_mm: Some(30.0),
+//!     ..PngOptions::default()
+//! };
+//! let png = epc.render_png(&options).unwrap();
+//! assert!(png.starts_with(b"\x89PNG"));
+//! ```
+
+use crate::qr::{MIN_MODULE_SIZE_MM, QUIET_ZONE, QrCode};
+use crate::{Epc, EpcError};
+use std::error::Error;
+use std::fmt::Display;
+
+/// The largest width of a rendered image in pixels
+pub const MAX_WIDTH_PX: usize = 10_000;
+
+const MM_PER_INCH: f64 = 25.4;
+
+/// Options for rendering an EPC as PNG
+#[derive(Debug, PartialEq, Clone)]
+pub struct PngOptions {
+    /// Resolution of the output device in dots per inch
+    pub dpi: u32,
+    /// Width of the whole image including the quiet zone in millimetres, by default the
+    /// smallest size with modules of at least [`MIN_MODULE_SIZE_MM`]
+    pub size_mm: Option<f64>,
+    /// Width of the light border around the code in modules
+    pub quiet_zone: usize,
+}
+
+impl Default for PngOptions {
+    fn default() -> Self {
+        PngOptions {
+            dpi: 300,
+            size_mm: None,
+            quiet_zone: QUIET_ZONE,
+        }
+    }
+}
+
+/// Possible errors when rendering an EPC as PNG
+#[derive(Debug, PartialEq, Clone)]
+pub enum PngError {
+    /// The EPC can't be encoded
+    Epc(EpcError),
+    /// The resolution is zero
+    InvalidResolution,
+    /// The size isn't a positive number of millimetres
+    InvalidSize { size_mm: f64 },
+    /// The image would be wider than [`MAX_WIDTH_PX`]
+    ImageTooLarge { max_px: usize },
+    /// The modules would be smaller than [`MIN_MODULE_SIZE_MM`] at the requested size and
+    /// resolution
+    ModuleTooSmall { actual_mm: f64, min_mm: f64 },
+    /// Writing the PNG failed
+    Encoding(String),
+}
+
+impl Display for PngError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PngError::Epc(e) => write!(f, "{}", e),
+            PngError::InvalidResolution => write!(f, "Resolution must be at least 1 dpi"),
+            PngError::InvalidSize { size_mm } => {
+                write!(f, "Invalid size {} mm, must be a positive number", size_mm)
+            }
+            PngError::ImageTooLarge { max_px } => {
+                write!(f, "Image too large (max {} pixels wide)", max_px)
+            }
+            PngError::ModuleTooSmall { actual_mm, min_mm } => write!(
+                f,
+                "Modules of {:.3} mm are too small to scan (min {} mm)",
+                actual_mm, min_mm
+            ),
+            PngError::Encoding(e) => write!(f, "PNG encoding failed: {}", e),
+        }
+    }
+}
+
+impl Error for PngError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PngError::Epc(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<EpcError> for PngError {
+    fn from(e: EpcError) -> Self {
+        PngError::Epc(e)
+    }
+}
+
+impl Epc {
+    /// Render the QR code of the EPC as PNG image for printing
+    pub fn render_png(&self, options: &PngOptions) -> Result<Vec<u8>, PngError> {
+        render(&self.to_qr_code()?, options)
+    }
+}
+
+/// Render a QR code as PNG image for printing
+pub fn render(code: &QrCode, options: &PngOptions) -> Result<Vec<u8>, PngError> {
+    if options.dpi == 0 {
+        return Err(PngError::InvalidResolution);
+    }
+    if let Some(size_mm) = options.size_mm
+        && !(size_mm.is_finite() && size_mm > 0.0)
+    {
+        return Err(PngError::InvalidSize { size_mm });
+    }
+    let modules = code.size() + 2 * options.quiet_zone;
+    let px_per_mm = options.dpi as f64 / MM_PER_INCH;
+    let module_px = match options.size_mm {
+        Some(mm) => (mm / modules as f64 * px_per_mm).round() as usize,
+        None => (MIN_MODULE_SIZE_MM * px_per_mm).ceil() as usize,
+    };
+    let module_mm = module_px as f64 / px_per_mm;
+    // Allow for the rounding error of the minimum module size itself
+    if module_mm < MIN_MODULE_SIZE_MM - 1e-9 {
+        return Err(PngError::ModuleTooSmall {
+            actual_mm: options
+                .size_mm
+                .map(|mm| mm / modules as f64)
+                .unwrap_or(module_mm),
+            min_mm: MIN_MODULE_SIZE_MM,
+        });
+    }
+
+    let width = modules
+        .checked_mul(module_px)
+        .filter(|width| *width <= MAX_WIDTH_PX)
+        .ok_or(PngError::ImageTooLarge {
+            max_px: MAX_WIDTH_PX,
+        })?;
+    let mut pixels = Vec::with_capacity(width * width);
+    for y in 0..width {
+        let module_y = (y / module_px).checked_sub(options.quiet_zone);
+        for x in 0..width {
+            let module_x = (x / module_px).checked_sub(options.quiet_zone);
+            let dark = match (module_x, module_y) {
+                (Some(mx), Some(my)) => code.is_dark(mx, my),
+                _ => false,
+            };
+            pixels.push(if dark { 0 } else { 255 });
+        }
+    }
+
+    let mut png = Vec::new();
+    let mut encoder = ::png::Encoder::new(&mut png, width as u32, width as u32);
+    encoder.set_color(::png::ColorType::Grayscale);
+    encoder.set_depth(::png::BitDepth::Eight);
+    let px_per_meter = (px_per_mm * 1000.0).round() as u32;
+    encoder.set_pixel_dims(Some(::png::PixelDimensions {
+        xppu: px_per_meter,
+        yppu: px_per_meter,
+        unit: ::png::Unit::Meter,
+    }));
+    let encoding_error = |e: ::png::EncodingError| PngError::Encoding(e.to_string());
+    let mut writer = encoder.write_header().map_err(encoding_error)?;
+    writer.write_image_data(&pixels).map_err(encoding_error)?;
+    writer.finish().map_err(encoding_error)?;
+    Ok(png)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_epc;
+
+    fn decode(png: &[u8]) -> (u32, Vec<u8>, Option<::png::PixelDimensions>) {
+        let decoder = ::png::Decoder::new(std::io::Cursor::new(png));
+        let mut reader = decoder.read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size().unwrap()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        let dims = reader.info().pixel_dims;
+        (info.width, pixels, dims)
+    }
+
+    #[test]
+    fn rendering_works() {
+        let epc = test_epc();
+        let code = epc.to_qr_code().unwrap();
+        let png = epc.render_png(&PngOptions::default()).unwrap();
+        let (width, pixels, dims) = decode(&png);
+        // 0.4 mm at 300 dpi are 4.72 pixels, rounded up to 5
+        assert_eq!(width as usize, (code.size() + 2 * QUIET_ZONE) * 5);
+        assert_eq!(dims.unwrap().xppu, 11811);
+        let px = |x: usize, y: usize| pixels[y * width as usize + x];
+        assert_eq!(px(0, 0), 255);
+        assert_eq!(px(4 * 5, 4 * 5), 0);
+        assert_eq!(px(4 * 5 + 4, 4 * 5 + 4), 0);
+        assert_eq!(px(5 * 5, 5 * 5), 255);
+    }
+
+    #[test]
+    fn rendering_in_requested_size_works() {
+        let options = PngOptions {
+            dpi: 600,
+            size_mm: Some(30.0),
+            quiet_zone: 2,
+        };
+        let png = test_epc().render_png(&options).unwrap();
+        let (width, _, _) = decode(&png);
+        // 37 modules of 30/37 mm at 600 dpi are 19 pixels each
+        assert_eq!(width, 37 * 19);
+    }
+
+    #[test]
+    fn too_small_modules_should_fail() {
+        let options = PngOptions {
+            size_mm: Some(10.0),
+            ..PngOptions::default()
+        };
+        let r = test_epc().render_png(&options);
+        assert!(matches!(r, Err(PngError::ModuleTooSmall { .. })));
+
+        let options = PngOptions {
+            dpi: 72,
+            size_mm: Some(17.0),
+            ..PngOptions::default()
+        };
+        let r = test_epc().render_png(&options);
+        assert!(matches!(r, Err(PngError::ModuleTooSmall { .. })));
+    }
+
+    #[test]
+    fn invalid_options_should_fail() {
+        let options = PngOptions {
+            dpi: 0,
+            ..PngOptions::default()
+        };
+        assert_eq!(
+            test_epc().render_png(&options),
+            Err(PngError::InvalidResolution)
+        );
+
+        for size_mm in [0.0, -30.0, f64::INFINITY] {
+            let options = PngOptions {
+                size_mm: Some(size_mm),
+                ..PngOptions::default()
+            };
+            assert_eq!(
+                test_epc().render_png(&options),
+                Err(PngError::InvalidSize { size_mm })
+            );
+        }
+        let options = PngOptions {
+            size_mm: Some(f64::NAN),
+            ..PngOptions::default()
+        };
+        assert!(matches!(
+            test_epc().render_png(&options),
+            Err(PngError::InvalidSize { .. })
+        ));
+
+        let options = PngOptions {
+            dpi: 1200,
+            size_mm: Some(1e9),
+            ..PngOptions::default()
+        };
+        assert_eq!(
+            test_epc().render_png(&options),
+            Err(PngError::ImageTooLarge {
+                max_px: MAX_WIDTH_PX
+            })
+        );
+        let options = PngOptions {
+            dpi: u32::MAX,
+            ..PngOptions::default()
+        };
+        assert_eq!(
+            test_epc().render_png(&options),
+            Err(PngError::ImageTooLarge {
+                max_px: MAX_WIDTH_PX
+            })
+        );
+    }
+}
@@ -0,0 +1,434 @@
+use std::fmt::Display;
+
+/// Category of an ISO 20022 ExternalPurpose1Code
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum PurposeCategory {
+    /// Card settlement
+    CardSettlement,
+    /// Cash management
+    CashManagement,
+    /// Commercial
+    Commercial,
+    /// Consumer
+    Consumer,
+    /// Dividend
+    Dividend,
+    /// E-payment
+    Epayment,
+    /// Finance
+    Finance,
+    /// General
+    General,
+    /// Government
+    Government,
+    /// Insurance
+    Insurance,
+    /// Investment
+    Investment,
+    /// Medical
+    Medical,
+    /// Pension
+    Pension,
+    /// Salary and benefits
+    SalaryAndBenefits,
+    /// Tax
+    Tax,
+    /// Trade services
+    TradeServices,
+    /// Transport
+    Transport,
+    /// Treasury
+    Treasury,
+    /// Utilities
+    Utilities,
+}
+
+impl Display for PurposeCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PurposeCategory::CardSettlement => write!(f, "Card settlement"),
+            PurposeCategory::CashManagement => write!(f, "Cash management"),
+            PurposeCategory::Commercial => write!(f, "Commercial"),
+            PurposeCategory::Consumer => write!(f, "Consumer"),
+            PurposeCategory::Dividend => write!(f, "Dividend"),
+            PurposeCategory::Epayment => write!(f, "E-payment"),
+            PurposeCategory::Finance => write!(f, "Finance"),
+            PurposeCategory::General => write!(f, "General"),
+            PurposeCategory::Government => write!(f, "Government"),
+            PurposeCategory::Insurance => write!(f, "Insurance"),
+            PurposeCategory::Investment => write!(f, "Investment"),
+            PurposeCategory::Medical => write!(f, "Medical"),
+            PurposeCategory::Pension => write!(f, "Pension"),
+            PurposeCategory::SalaryAndBenefits => write!(f, "Salary and benefits"),
+            PurposeCategory::Tax => write!(f, "Tax"),
+            PurposeCategory::TradeServices => write!(f, "Trade services"),
+            PurposeCategory::Transport => write!(f, "Transport"),
+            PurposeCategory::Treasury => write!(f, "Treasury"),
+            PurposeCategory::Utilities => write!(f, "Utilities"),
+        }
+    }
+}
+
+/// An entry of the ISO 20022 ExternalPurpose1Code list
+pub(crate) struct PurposeCode {
+    /// The four character code
+    pub(crate) code: &'static str,
+    /// Category of the code
+    pub(crate) category: PurposeCategory,
+    /// Short description of the code
+    pub(crate) description: &'static str,
+}
+
+/// Look up a code of the ExternalPurpose1Code list
+pub(crate) fn lookup(code: &str) -> Option<&'static PurposeCode> {
+    PURPOSE_CODES
+        .binary_search_by(|p| p.code.cmp(code))
+        .ok()
+        .map(|i| &PURPOSE_CODES[i])
+}
+
+macro_rules! codes {
+    ($($code:literal $category:ident $description:literal,)*) => {
+        [$(PurposeCode {
+            code: $code,
+            category: PurposeCategory::$category,
+            description: $description,
+        },)*]
+    };
+}
+
+/// The codes of the ISO 20022 ExternalPurpose1Code list, sorted by code
+pub(crate) const PURPOSE_CODES: [PurposeCode; 305] = codes![
+    "ACCT" CashManagement "Account management",
+    "ADCS" Commercial "Advisory, donation or copyright services",
+    "ADMG" Commercial "Administrative management",
+    "ADVA" Commercial "Advance payment",
+    "AEMP" SalaryAndBenefits "Active employment policy",
+    "AGRT" Commercial "Agricultural transfer",
+    "AIRB" Transport "Air transport",
+    "ALLW" SalaryAndBenefits "Allowance",
+    "ALMY" Consumer "Alimony payment",
+    "AMEX" CardSettlement "American Express card settlement",
+    "ANNI" Finance "Annuity",
+    "ANTS" Medical "Anesthesia services",
+    "AREN" Commercial "Accounts receivables entry",
+    "AUCO" CashManagement "Authenticated collections",
+    "B112" Investment "US mutual fund trailer fee payment",
+    "BBSC" SalaryAndBenefits "Baby bonus scheme",
+    "BCDM" CashManagement "Bearer cheque domestic",
+    "BCFG" CashManagement "Bearer cheque foreign",
+    "BECH" SalaryAndBenefits "Child benefit",
+    "BENE" SalaryAndBenefits "Unemployment or disability benefit",
+    "BEXP" Commercial "Business expenses",
+    "BFWD" Treasury "Bond forward",
+    "BKDF" Finance "Bank loan delayed draw funding",
+    "BKFE" Finance "Bank fees",
+    "BKFM" Finance "Bank loan funding memo",
+    "BKIP" Finance "Bank loan accrued interest payment",
+    "BKPP" Finance "Bank loan principal paydown",
+    "BLDM" Consumer "Building maintenance",
+    "BNET" Treasury "Bond forward netting",
+    "BOCE" Commercial "Back office conversion entry",
+    "BOND" Investment "Bonds",
+    "BONU" SalaryAndBenefits "Bonus payment",
+    "BR12" Investment "US mutual fund trailer fee rebate payment",
+    "BUSB" Transport "Bus transport",
+    "CABD" Investment "Corporate actions on bonds",
+    "CAEQ" Investment "Corporate actions on equities",
+    "CAFI" Investment "Custodian management fee in-house",
+    "CASH" CashManagement "Cash management transfer",
+    "CBCR" CardSettlement "Credit card",
+    "CBFF" SalaryAndBenefits "Capital building fringe fortune",
+    "CBFR" SalaryAndBenefits "Capital building fringe fortune for retirement",
+    "CBLK" CardSettlement "Card bulk clearing",
+    "CBTV" Utilities "Cable TV bill",
+    "CCHD" Government "Cash compensation, helplessness, disability",
+    "CCIR" Treasury "Cross currency interest rate swap",
+    "CCPC" Treasury "CCP cleared initial margin",
+    "CCPM" Treasury "CCP cleared variation margin",
+    "CCRD" CardSettlement "Credit card payment",
+    "CCSM" Treasury "CCP cleared initial margin segregated cash",
+    "CDBL" CardSettlement "Credit card bill",
+    "CDCB" CardSettlement "Card payment with cash back",
+    "CDCD" CardSettlement "Cash disbursement",
+    "CDCS" CardSettlement "Cash disbursement with surcharging",
+    "CDDP" CardSettlement "Card deferred payment",
+    "CDEP" Treasury "Credit default event payment",
+    "CDOC" CardSettlement "Original credit",
+    "CDQC" CardSettlement "Quasi cash",
+    "CFDI" Finance "Capital falling due in-house",
+    "CFEE" General "Cancellation fee",
+    "CGDD" CardSettlement "Card generated direct debit",
+    "CHAR" Consumer "Charity payment",
+    "CLPR" Finance "Car loan principal repayment",
+    "CMDT" Treasury "Commodity transfer",
+    "COLL" CashManagement "Collection payment",
+    "COMC" Commercial "Commercial payment",
+    "COMM" Commercial "Commission",
+    "COMP" Commercial "Compensation payment",
+    "COMT" Consumer "Consumer third party consolidated payment",
+    "CORT" Treasury "Trade settlement payment",
+    "COST" Commercial "Costs",
+    "CPEN" Investment "Cash penalties",
+    "CPKC" Transport "Car park charges",
+    "CPYR" Commercial "Copyright",
+    "CRDS" Treasury "Credit default swap",
+    "CRPR" Treasury "Cross product",
+    "CRSP" Treasury "Credit support",
+    "CRTL" Finance "Credit line",
+    "CSDB" CashManagement "Cash disbursement",
+    "CSLP" SalaryAndBenefits "Company social loan payment to bank",
+    "CVCF" Medical "Convalescent care facility",
+    "DBCR" CardSettlement "Debit card",
+    "DBTC" Commercial "Debit collection payment",
+    "DCRD" CardSettlement "Debit card payment",
+    "DEPD" SalaryAndBenefits "Dependent support payment",
+    "DEPT" Finance "Deposit",
+    "DERI" Treasury "Derivatives",
+    "DIVD" Dividend "Dividend",
+    "DMEQ" Medical "Durable medical equipment",
+    "DNTS" Medical "Dental services",
+    "DSMT" CashManagement "Printed order disbursement",
+    "DVPM" Investment "Deliver against payment",
+    "ECPG" Epayment "Guaranteed e-payment",
+    "ECPR" Epayment "E-payment return",
+    "ECPU" Epayment "Non-guaranteed e-payment",
+    "EDUC" Consumer "Education",
+    "ELEC" Utilities "Electricity bill",
+    "ENRG" Utilities "Energies",
+    "EPAY" Epayment "E-payment",
+    "EQPT" Treasury "Equity option",
+    "EQUS" Treasury "Equity swap",
+    "ESTX" Tax "Estate tax",
+    "ETUP" CashManagement "E-purse top up",
+    "EXPT" Treasury "Exotic option",
+    "EXTD" Treasury "Exchange traded derivatives",
+    "FACT" Treasury "Factor update related payment",
+    "FAND" Government "Financial aid in case of natural disaster",
+    "FCOL" CardSettlement "Fee collection",
+    "FCPM" Commercial "Late payment of fees and charges",
+    "FEES" Commercial "Payment of fees",
+    "FERB" Transport "Ferry",
+    "FIXI" Treasury "Fixed income",
+    "FLCR" CardSettlement "Fleet card",
+    "FNET" Treasury "Futures netting payment",
+    "FORW" Treasury "Forward foreign exchange",
+    "FREX" Treasury "Foreign exchange",
+    "FUTR" Treasury "Futures",
+    "FWBC" Treasury "Forward broker owned cash collateral",
+    "FWCC" Treasury "Forward clearing margin collateral",
+    "FWSB" Treasury "Forward broker owned cash collateral segregated",
+    "FWSC" Treasury "Forward clearing margin segregated collateral",
+    "FXNT" Treasury "Foreign exchange related netting",
+    "GAFA" Government "Government family allowance",
+    "GAHO" Government "Government housing allowance",
+    "GAMB" Consumer "Gambling or wagering payment",
+    "GASB" Utilities "Gas bill",
+    "GDDS" Commercial "Purchase or sale of goods",
+    "GDSV" Commercial "Purchase or sale of goods and services",
+    "GFRP" SalaryAndBenefits "Guarantee fund rights payment",
+    "GIFT" Consumer "Gift",
+    "GOVI" Insurance "Government insurance",
+    "GOVT" Government "Government payment",
+    "GSCB" Commercial "Purchase or sale of goods and services with cash back",
+    "GSTX" Tax "Goods and services tax",
+    "GVEA" SalaryAndBenefits "Austrian government employees category A",
+    "GVEB" SalaryAndBenefits "Austrian government employees category B",
+    "GVEC" SalaryAndBenefits "Austrian government employees category C",
+    "GVED" SalaryAndBenefits "Austrian government employees category D",
+    "GWLT" Government "Government war legislation transfer",
+    "HEDG" Treasury "Hedging",
+    "HLRP" Finance "Property loan repayment",
+    "HLST" Finance "Property loan settlement",
+    "HLTC" Medical "Home health care",
+    "HLTI" Insurance "Health insurance",
+    "HREC" SalaryAndBenefits "Housing related contribution",
+    "HSPC" Medical "Hospital care",
+    "HSTX" Tax "Housing tax",
+    "ICCP" CardSettlement "Irrevocable credit card payment",
+    "ICRF" Medical "Intermediate care facility",
+    "IDCP" CardSettlement "Irrevocable debit card payment",
+    "IHRP" Commercial "Instalment hire purchase agreement",
+    "INPC" Insurance "Insurance premium car",
+    "INPR" Insurance "Insurance premium refund",
+    "INSM" Finance "Installment",
+    "INSU" Insurance "Insurance premium",
+    "INTC" CashManagement "Intra company payment",
+    "INTE" Finance "Interest",
+    "INTP" CashManagement "Intra platform transfer",
+    "INTX" Tax "Income tax",
+    "INVS" Investment "Investment and securities",
+    "IVPT" Commercial "Invoice payment",
+    "LBIN" Treasury "Lending buy-in netting",
+    "LBRI" Insurance "Labor insurance",
+    "LCOL" Treasury "Lending cash collateral free movement",
+    "LFEE" Treasury "Lending fees",
+    "LICF" Commercial "License fee",
+    "LIFI" Insurance "Life insurance",
+    "LIMA" CashManagement "Liquidity management",
+    "LMEQ" Treasury "Lending equity marked-to-market cash collateral",
+    "LMFI" Treasury "Lending fixed income marked-to-market cash collateral",
+    "LMRK" Treasury "Lending unspecified type of marked-to-market cash collateral",
+    "LOAN" Finance "Loan",
+    "LOAR" Finance "Loan repayment",
+    "LOTT" Consumer "Lottery payment",
+    "LREB" Treasury "Lending rebate payments",
+    "LREV" Treasury "Lending revenue payments",
+    "LSFL" Treasury "Lending claim payment",
+    "LTCF" Medical "Long term care facility",
+    "MAFC" SalaryAndBenefits "Medical aid fund contribution",
+    "MARF" SalaryAndBenefits "Medical aid refund",
+    "MARG" Treasury "Daily margin on listed derivatives",
+    "MBSB" Treasury "MBS broker owned cash collateral",
+    "MBSC" Treasury "MBS clearing margin collateral",
+    "MCDM" CashManagement "Multi currency cheque domestic",
+    "MCFG" CashManagement "Multi currency cheque foreign",
+    "MDCS" Medical "Medical services",
+    "MGCC" Treasury "Futures initial margin",
+    "MGSC" Treasury "Futures initial margin client owned segregated cash collateral",
+    "MOMA" Treasury "Money market",
+    "MP2B" Epayment "Mobile person to business payment",
+    "MP2P" Consumer "Mobile person to person payment",
+    "MSVC" Commercial "Multiple service types",
+    "MTUP" CashManagement "Mobile top up",
+    "NETT" CashManagement "Netting",
+    "NITX" Tax "Net income tax",
+    "NOWS" General "Not otherwise specified",
+    "NWCH" Utilities "Network charge",
+    "NWCM" Utilities "Network communication",
+    "OCCC" Treasury "Client owned OCC pledged collateral",
+    "OCDM" CashManagement "Order cheque domestic",
+    "OCFG" CashManagement "Order cheque foreign",
+    "OFEE" General "Opening fee",
+    "OPBC" Treasury "OTC option broker owned cash collateral",
+    "OPCC" Treasury "OTC option clearing margin collateral",
+    "OPSB" Treasury "OTC option broker owned segregated cash collateral",
+    "OPSC" Treasury "OTC option clearing margin segregated cash collateral",
+    "OPTN" Treasury "Foreign exchange option",
+    "OTCD" Treasury "OTC derivatives",
+    "OTHR" General "Other",
+    "OTLC" Utilities "Other telecom related bill",
+    "PADD" Commercial "Preauthorized debit",
+    "PAYR" SalaryAndBenefits "Payroll",
+    "PENO" Government "Payment based on enforcement order",
+    "PENS" Pension "Pension payment",
+    "PHON" Utilities "Telephone bill",
+    "POPE" Commercial "Point of purchase entry",
+    "PPTI" Insurance "Property insurance",
+    "PRCP" Commercial "Price payment",
+    "PRME" Treasury "Precious metal",
+    "PTSP" Commercial "Payment terms",
+    "PTXP" Tax "Property tax",
+    "RAPI" Commercial "Rapid payment instruction",
+    "RCKE" Commercial "Represented check entry",
+    "RCPT" CashManagement "Receipt payment",
+    "RDTX" Tax "Road tax",
+    "REBT" Commercial "Rebate",
+    "REFU" Commercial "Refund",
+    "RELG" Commercial "Rental lease general",
+    "RENT" Consumer "Rent",
+    "REOD" Finance "Account overdraft repayment",
+    "REPO" Treasury "Repurchase agreement",
+    "RHBS" SalaryAndBenefits "Rehabilitation support",
+    "RIMB" General "Reimbursement of a previous erroneous transaction",
+    "RINP" Finance "Recurring installment payment",
+    "RLWY" Transport "Railway",
+    "ROYA" Commercial "Royalties",
+    "RPBC" Treasury "Bilateral repo broker owned collateral",
+    "RPCC" Treasury "Repo clearing margin collateral",
+    "RPNT" Treasury "Bilateral repo internet netting",
+    "RPSB" Treasury "Bilateral repo broker owned segregated cash collateral",
+    "RPSC" Treasury "Bilateral repo clearing margin segregated cash collateral",
+    "RRBN" CashManagement "Round robin",
+    "RRCT" General "Reimbursement of a received credit transfer",
+    "RRTP" General "Related request to pay",
+    "RVPM" Investment "Receive against payment",
+    "RVPO" Treasury "Reverse repurchase agreement",
+    "SALA" SalaryAndBenefits "Salary payment",
+    "SASW" CashManagement "ATM",
+    "SAVG" Finance "Savings",
+    "SBSC" Treasury "Securities buy sell sell buy back",
+    "SCIE" Treasury "Single currency interest rate swap exotic",
+    "SCIR" Treasury "Single currency interest rate swap",
+    "SCRP" Treasury "Securities cross products",
+    "SCVE" Commercial "Purchase or sale of services",
+    "SECU" Investment "Securities",
+    "SEPI" Investment "Securities purchase in-house",
+    "SERV" Commercial "Service charges",
+    "SHBC" Treasury "Broker owned collateral short sale",
+    "SHCC" Treasury "Short sale clearing margin collateral",
+    "SHSL" Treasury "Short sell",
+    "SLEB" Treasury "Securities lending and borrowing",
+    "SLOA" Treasury "Secured loan",
+    "SLPI" CashManagement "Payment slip instruction",
+    "SPLT" CashManagement "Split payments",
+    "SPSP" SalaryAndBenefits "Salary pension sum payment",
+    "SSBE" SalaryAndBenefits "Social security benefit",
+    "STDY" Consumer "Study",
+    "SUBS" Commercial "Subscription",
+    "SUPP" Commercial "Supplier payment",
+    "SWBC" Treasury "Swap broker owned cash collateral",
+    "SWCC" Treasury "Swap clearing margin collateral",
+    "SWFP" Treasury "Swap contract final payment",
+    "SWPP" Treasury "Swap contract partial payment",
+    "SWPT" Treasury "Swaption",
+    "SWRS" Treasury "Swap contract reset payment",
+    "SWSB" Treasury "Swaps broker owned segregated cash collateral",
+    "SWSC" Treasury "Swaps clearing margin segregated cash collateral",
+    "SWUF" Treasury "Swap contract upfront payment",
+    "TAXR" Tax "Tax refund",
+    "TAXS" Tax "Tax payment",
+    "TBAN" Treasury "TBA pair-off netting",
+    "TBAS" Treasury "To be announced",
+    "TBBC" Treasury "TBA broker owned cash collateral",
+    "TBCC" Treasury "TBA clearing margin collateral",
+    "TBIL" Utilities "Telecommunications bill",
+    "TCSC" Utilities "Town council service charges",
+    "TELI" Commercial "Telephone initiated transaction",
+    "TLRF" Investment "Non-US mutual fund trailer fee payment",
+    "TLRR" Investment "Non-US mutual fund trailer fee rebate payment",
+    "TMPG" Treasury "TMPG claim payment",
+    "TPRI" Treasury "Tri-party repo interest",
+    "TPRP" Treasury "Tri-party repo netting",
+    "TRAD" TradeServices "Trade services",
+    "TRCP" Treasury "Treasury cross product",
+    "TREA" Treasury "Treasury payment",
+    "TRFD" Finance "Trust fund",
+    "TRNC" CashManagement "Truncated payment slip",
+    "TRPT" Transport "Road pricing",
+    "TRVC" CashManagement "Traveller cheque",
+    "UBIL" Utilities "Utilities",
+    "UNIT" Investment "Unit trust purchase",
+    "VATX" Tax "Value added tax payment",
+    "VIEW" Medical "Vision care",
+    "WEBI" Commercial "Internet initiated entry",
+    "WHLD" Tax "With holding",
+    "WTER" Utilities "Water bill",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catalogue_is_consistent() {
+        assert!(PURPOSE_CODES.windows(2).all(|w| w[0].code < w[1].code));
+        assert!(PURPOSE_CODES.iter().all(|p| {
+            p.code.len() == 4
+                && p.code
+                    .chars()
+                    .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+                && !p.description.is_empty()
+        }));
+    }
+
+    #[test]
+    fn looking_up_codes_works() {
+        let sala = lookup("SALA").unwrap();
+        assert_eq!(sala.category, PurposeCategory::SalaryAndBenefits);
+        assert_eq!(sala.description, "Salary payment");
+        assert!(lookup("MP2P").is_some());
+        assert!(lookup("XXXX").is_none());
+        assert!(lookup("sala").is_none());
+    }
+}
@@ -0,0 +1,668 @@
+//! QR code generation following the EPC guidelines
+//!
+//! The payload is encoded in byte mode with error correction level M in a QR code of at most
+//! version 13, as EPC069-12 requires.
+//!
+//! # Examples
+//! ```
+//! use epcgen::{CharacterSet, Epc, Identification, Version};
+//!
+//! let epc = Epc::builder()
+//!     .version(Version::V2)
+//!     .character_set(CharacterSet::UTF8)
+//!     .identification(Identification::Sct)
+//!     .beneficiary("Codeberg e.V.")
+//!     .iban("DE90 8306 5408 0004 1042 42")
+//!     .build()
+//!     .unwrap();
+//! let code = epc.to_qr_code().unwrap();
+//! assert_eq!(code.version(), 4);
+//! assert_eq!(code.size(), 33);
+//! ```
+
+use crate::epcgen::MAX_PAYLOAD_LEN;
+use crate::{CharacterSet, Epc, EpcError};
+
+/// The highest QR code version allowed for EPCs
+pub const MAX_VERSION: u8 = 13;
+
+/// Width of the light border around a QR code in modules, which scanners need to find it
+pub const QUIET_ZONE: usize = 4;
+
+/// The smallest module size in millimetres that scanners reliably read in print
+pub const MIN_MODULE_SIZE_MM: f64 = 0.4;
+
+/// Codewords of a version at error correction level M: total number of codewords, error
+/// correction codewords per block, number of blocks in group 1 and in group 2
+const CODEWORDS: [(usize, usize, usize, usize); MAX_VERSION as usize] = [
+    (26, 10, 1, 0),
+    (44, 16, 1, 0),
+    (70, 26, 1, 0),
+    (100, 18, 2, 0),
+    (134, 24, 2, 0),
+    (172, 16, 4, 0),
+    (196, 18, 4, 0),
+    (242, 22, 2, 2),
+    (292, 22, 3, 2),
+    (346, 26, 4, 1),
+    (404, 30, 1, 4),
+    (466, 22, 6, 2),
+    (532, 22, 8, 1),
+];
+
+/// Centre coordinates of the alignment patterns of a version
+const ALIGNMENT_PATTERNS: [&[usize]; MAX_VERSION as usize] = [
+    &[],
+    &[6, 18],
+    &[6, 22],
+    &[6, 26],
+    &[6, 30],
+    &[6, 34],
+    &[6, 22, 38],
+    &[6, 24, 42],
+    &[6, 26, 46],
+    &[6, 28, 50],
+    &[6, 30, 54],
+    &[6, 32, 58],
+    &[6, 34, 62],
+];
+
+/// A QR code as a square matrix of dark and light modules
+#[derive(Debug, PartialEq, Clone)]
+pub struct QrCode {
+    version: u8,
+    eci: Option<u32>,
+    size: usize,
+    modules: Vec<bool>,
+}
+
+impl QrCode {
+    /// The QR code version, from 1 up to [`MAX_VERSION`]
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// The ECI designator announcing the character set, if any
+    pub fn eci(&self) -> Option<u32> {
+        self.eci
+    }
+
+    /// Number of modules per side, without the quiet zone
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Whether the module in column `x` and row `y` is dark, light outside of the code
+    pub fn is_dark(&self, x: usize, y: usize) -> bool {
+        x < self.size && y < self.size && self.modules[y * self.size + x]
+    }
+
+    /// Encode a payload in the smallest possible version, with an ECI designator if given
+    fn encode(payload: &[u8], eci: Option<u32>) -> Option<QrCode> {
+        let header_len = eci.map(|_| 12).unwrap_or(0) + 4;
+        let version = (1..=MAX_VERSION).find(|&v| {
+            let (data, _) = capacity(v);
+            header_len + count_len(v) + payload.len() * 8 <= data * 8
+        })?;
+
+        let (data_len, _) = capacity(version);
+        let mut bits = Bits::default();
+        if let Some(eci) = eci {
+            bits.push(0b0111, 4);
+            bits.push(eci, 8);
+        }
+        bits.push(0b0100, 4);
+        bits.push(payload.len() as u32, count_len(version));
+        for b in payload {
+            bits.push(*b as u32, 8);
+        }
+        let terminator = (data_len * 8 - bits.len).min(4);
+        bits.push(0, terminator);
+        bits.push(0, (8 - bits.len % 8) % 8);
+        for pad in [0xEC, 0x11].into_iter().cycle() {
+            if bits.bytes.len() == data_len {
+                break;
+            }
+            bits.push(pad, 8);
+        }
+
+        let codewords = add_error_correction(version, &bits.bytes);
+        let mut matrix = Matrix::new(version);
+        matrix.draw_function_patterns();
+        matrix.draw_codewords(&codewords);
+        let mask = (0..8)
+            .min_by_key(|&mask| {
+                matrix.apply_mask(mask);
+                matrix.draw_format_bits(mask);
+                let penalty = matrix.penalty();
+                matrix.apply_mask(mask);
+                penalty
+            })
+            .unwrap_or(0);
+        matrix.apply_mask(mask);
+        matrix.draw_format_bits(mask);
+
+        Some(QrCode {
+            version,
+            eci,
+            size: matrix.size,
+            modules: matrix.modules,
+        })
+    }
+}
+
+impl Epc {
+    /// Encode the payload in a QR code with error correction level M and at most version 13
+    ///
+    /// The character set is announced with an ECI designator unless it is ISO-8859-1, which
+    /// scanners assume by default. A designator which would exceed version 13 is left out, as
+    /// the payload declares its character set itself.
+    pub fn to_qr_code(&self) -> Result<QrCode, EpcError> {
+        let payload = self.to_bytes()?;
+        QrCode::encode(&payload, eci(self.character_set()))
+            .or_else(|| QrCode::encode(&payload, None))
+            .ok_or(EpcError::PayloadTooLong {
+                max: MAX_PAYLOAD_LEN,
+                actual: payload.len(),
+            })
+    }
+}
+
+/// The ECI designator of a character set, `None` for the default ISO-8859-1
+fn eci(character_set: CharacterSet) -> Option<u32> {
+    match character_set {
+        CharacterSet::UTF8 => Some(26),
+        CharacterSet::Iso8859_1 => None,
+        CharacterSet::Iso8859_2 => Some(4),
+        CharacterSet::Iso8859_4 => Some(6),
+        CharacterSet::Iso8859_5 => Some(7),
+        CharacterSet::Iso8859_7 => Some(9),
+        CharacterSet::Iso8859_10 => Some(12),
+        CharacterSet::Iso8859_15 => Some(17),
+    }
+}
+
+/// Number of data and error correction codewords of a version
+fn capacity(version: u8) -> (usize, usize) {
+    let (total, ec_per_block, group1, group2) = CODEWORDS[version as usize - 1];
+    let ec = ec_per_block * (group1 + group2);
+    (total - ec, ec)
+}
+
+/// Length of the character count indicator in byte mode
+fn count_len(version: u8) -> usize {
+    if version < 10 { 8 } else { 16 }
+}
+
+/// Buffer for writing a bit stream
+#[derive(Default)]
+struct Bits {
+    bytes: Vec<u8>,
+    len: usize,
+}
+
+impl Bits {
+    /// Append the lowest `count` bits of the value, most significant first
+    fn push(&mut self, value: u32, count: usize) {
+        for i in (0..count).rev() {
+            if self.len.is_multiple_of(8) {
+                self.bytes.push(0);
+            }
+            if (value >> i) & 1 == 1 {
+                let last = self.bytes.len() - 1;
+                self.bytes[last] |= 0x80 >> (self.len % 8);
+            }
+            self.len += 1;
+        }
+    }
+}
+
+/// Split the data into blocks, add the Reed-Solomon error correction codewords to each of
+/// them and interleave the result
+fn add_error_correction(version: u8, data: &[u8]) -> Vec<u8> {
+    let (total, ec_len, group1, group2) = CODEWORDS[version as usize - 1];
+    let blocks = group1 + group2;
+    let short_len = data.len() / blocks;
+    let generator = reed_solomon_generator(ec_len);
+
+    let mut data_blocks = Vec::with_capacity(blocks);
+    let mut ec_blocks = Vec::with_capacity(blocks);
+    let mut start = 0;
+    for i in 0..blocks {
+        let len = if i < group1 { short_len } else { short_len + 1 };
+        let block = &data[start..start + len];
+        ec_blocks.push(reed_solomon_remainder(block, &generator));
+        data_blocks.push(block);
+        start += len;
+    }
+
+    let mut codewords = Vec::with_capacity(total);
+    for i in 0..=short_len {
+        codewords.extend(data_blocks.iter().filter_map(|b| b.get(i)));
+    }
+    for i in 0..ec_len {
+        codewords.extend(ec_blocks.iter().map(|b| b[i]));
+    }
+    codewords
+}
+
+/// Multiply in GF(256) modulo the QR code polynomial x^8 + x^4 + x^3 + x^2 + 1
+fn gf_mul(x: u8, y: u8) -> u8 {
+    let mut z: u16 = 0;
+    for i in (0..8).rev() {
+        z = (z << 1) ^ ((z >> 7) * 0x11D);
+        z ^= ((y as u16 >> i) & 1) * x as u16;
+    }
+    z as u8
+}
+
+/// Coefficients of the generator polynomial of the given degree, highest first and without
+/// the leading 1
+fn reed_solomon_generator(degree: usize) -> Vec<u8> {
+    let mut result = vec![0; degree];
+    result[degree - 1] = 1;
+    let mut root = 1;
+    for _ in 0..degree {
+        for j in 0..degree {
+            result[j] = gf_mul(result[j], root);
+            if j + 1 < degree {
+                result[j] ^= result[j + 1];
+            }
+        }
+        root = gf_mul(root, 2);
+    }
+    result
+}
+
+/// The error correction codewords of a block
+fn reed_solomon_remainder(data: &[u8], generator: &[u8]) -> Vec<u8> {
+    let mut remainder = vec![0; generator.len()];
+    for b in data {
+        let factor = b ^ remainder.remove(0);
+        remainder.push(0);
+        for (r, g) in remainder.iter_mut().zip(generator) {
+            *r ^= gf_mul(*g, factor);
+        }
+    }
+    remainder
+}
+
+/// Modules of a QR code under construction, with the function patterns marked as such
+struct Matrix {
+    version: u8,
+    size: usize,
+    modules: Vec<bool>,
+    function: Vec<bool>,
+}
+
+impl Matrix {
+    fn new(version: u8) -> Self {
+        let size = version as usize * 4 + 17;
+        Matrix {
+            version,
+            size,
+            modules: vec![false; size * size],
+            function: vec![false; size * size],
+        }
+    }
+
+    fn get(&self, x: usize, y: usize) -> bool {
+        self.modules[y * self.size + x]
+    }
+
+    fn set_function(&mut self, x: usize, y: usize, dark: bool) {
+        self.modules[y * self.size + x] = dark;
+        self.function[y * self.size + x] = true;
+    }
+
+    fn draw_function_patterns(&mut self) {
+        for i in 0..self.size {
+            self.set_function(6, i, i % 2 == 0);
+            self.set_function(i, 6, i % 2 == 0);
+        }
+
+        let last = self.size - 7;
+        for (x, y) in [(3, 3), (last + 3, 3), (3, last + 3)] {
+            self.draw_finder_pattern(x, y);
+        }
+
+        let positions = ALIGNMENT_PATTERNS[self.version as usize - 1];
+        for &x in positions {
+            for &y in positions {
+                let overlaps_finder = (x == 6 && (y == 6 || y == last)) || (x == last && y == 6);
+                if !overlaps_finder {
+                    self.draw_alignment_pattern(x, y);
+                }
+            }
+        }
+
+        // Reserve the format areas, the final bits are drawn after masking
+        self.draw_format_bits(0);
+        self.draw_version_bits();
+    }
+
+    /// Draw a finder pattern together with its light separator around the given centre
+    fn draw_finder_pattern(&mut self, x: usize, y: usize) {
+        for dy in -4..=4_isize {
+            for dx in -4..=4_isize {
+                let (xx, yy) = (x as isize + dx, y as isize + dy);
+                if (0..self.size as isize).contains(&xx) && (0..self.size as isize).contains(&yy) {
+                    let distance = dx.abs().max(dy.abs());
+                    self.set_function(xx as usize, yy as usize, distance != 2 && distance != 4);
+                }
+            }
+        }
+    }
+
+    fn draw_alignment_pattern(&mut self, x: usize, y: usize) {
+        for dy in -2..=2_isize {
+            for dx in -2..=2_isize {
+                let distance = dx.abs().max(dy.abs());
+                self.set_function(
+                    (x as isize + dx) as usize,
+                    (y as isize + dy) as usize,
+                    distance != 1,
+                );
+            }
+        }
+    }
+
+    /// Draw both copies of the format information for error correction level M
+    fn draw_format_bits(&mut self, mask: u8) {
+        let data = mask as u32;
+        let mut remainder = data;
+        for _ in 0..10 {
+            remainder = (remainder << 1) ^ ((remainder >> 9) * 0x537);
+        }
+        let bits = ((data << 10) | remainder) ^ 0x5412;
+        let bit = |i: usize| (bits >> i) & 1 == 1;
+
+        for i in 0..=5 {
+            self.set_function(8, i, bit(i));
+        }
+        self.set_function(8, 7, bit(6));
+        self.set_function(8, 8, bit(7));
+        self.set_function(7, 8, bit(8));
+        for i in 9..15 {
+            self.set_function(14 - i, 8, bit(i));
+        }
+
+        for i in 0..8 {
+            self.set_function(self.size - 1 - i, 8, bit(i));
+        }
+        for i in 8..15 {
+            self.set_function(8, self.size - 15 + i, bit(i));
+        }
+        self.set_function(8, self.size - 8, true);
+    }
+
+    /// Draw both copies of the version information, which versions from 7 on carry
+    fn draw_version_bits(&mut self) {
+        if self.version < 7 {
+            return;
+        }
+        let data = self.version as u32;
+        let mut remainder = data;
+        for _ in 0..12 {
+            remainder = (remainder << 1) ^ ((remainder >> 11) * 0x1F25);
+        }
+        let bits = (data << 12) | remainder;
+        for i in 0..18 {
+            let dark = (bits >> i) & 1 == 1;
+            let (a, b) = (self.size - 11 + i % 3, i / 3);
+            self.set_function(a, b, dark);
+            self.set_function(b, a, dark);
+        }
+    }
+
+    /// Place the codewords in the zigzag order of two-module wide columns
+    fn draw_codewords(&mut self, codewords: &[u8]) {
+        let mut i = 0;
+        let mut right = self.size - 1;
+        while right >= 1 {
+            if right == 6 {
+                right = 5;
+            }
+            for vertical in 0..self.size {
+                for j in 0..2 {
+                    let x = right - j;
+                    let upward = (right + 1) & 2 == 0;
+                    let y = if upward {
+                        self.size - 1 - vertical
+                    } else {
+                        vertical
+                    };
+                    if !self.function[y * self.size + x] && i < codewords.len() * 8 {
+                        self.modules[y * self.size + x] =
+                            (codewords[i / 8] >> (7 - i % 8)) & 1 == 1;
+                        i += 1;
+                    }
+                }
+            }
+            if right < 2 {
+                break;
+            }
+            right -= 2;
+        }
+    }
+
+    /// Invert the data modules selected by the mask pattern, applying it twice undoes it
+    fn apply_mask(&mut self, mask: u8) {
+        for y in 0..self.size {
+            for x in 0..self.size {
+                let invert = match mask {
+                    0 => (x + y) % 2 == 0,
+                    1 => y % 2 == 0,
+                    2 => x % 3 == 0,
+                    3 => (x + y) % 3 == 0,
+                    4 => (x / 3 + y / 2) % 2 == 0,
+                    5 => x * y % 2 + x * y % 3 == 0,
+                    6 => (x * y % 2 + x * y % 3) % 2 == 0,
+                    _ => ((x + y) % 2 + x * y % 3) % 2 == 0,
+                };
+                if invert && !self.function[y * self.size + x] {
+                    self.modules[y * self.size + x] ^= true;
+                }
+            }
+        }
+    }
+
+    /// Penalty score of the current modules, the mask with the lowest score is used
+    fn penalty(&self) -> usize {
+        let mut penalty = 0;
+        for horizontal in [true, false] {
+            for i in 0..self.size {
+                let line: Vec<bool> = (0..self.size)
+                    .map(|j| {
+                        if horizontal {
+                            self.get(j, i)
+                        } else {
+                            self.get(i, j)
+                        }
+                    })
+                    .collect();
+                penalty += self.line_penalty(&line);
+            }
+        }
+
+        for y in 0..self.size - 1 {
+            for x in 0..self.size - 1 {
+                let dark = self.get(x, y);
+                if dark == self.get(x + 1, y)
+                    && dark == self.get(x, y + 1)
+                    && dark == self.get(x + 1, y + 1)
+                {
+                    penalty += 3;
+                }
+            }
+        }
+
+        let total = self.size * self.size;
+        let dark = self.modules.iter().filter(|m| **m).count();
+        let k = (dark * 20).abs_diff(total * 10).div_ceil(total) - 1;
+        penalty + k * 10
+    }
+
+    /// Penalty for runs of same coloured modules and finder-like patterns in a row or column
+    fn line_penalty(&self, line: &[bool]) -> usize {
+        let mut penalty = 0;
+        let mut history = RunHistory::new(self.size);
+        let mut run_dark = false;
+        let mut run_len = 0;
+        for &dark in line {
+            if dark == run_dark {
+                run_len += 1;
+                if run_len == 5 {
+                    penalty += 3;
+                } else if run_len > 5 {
+                    penalty += 1;
+                }
+            } else {
+                history.push(run_len);
+                if !run_dark {
+                    penalty += history.finder_patterns() * 40;
+                }
+                run_dark = dark;
+                run_len = 1;
+            }
+        }
+        if run_dark {
+            history.push(run_len);
+            run_len = 0;
+        }
+        history.push(run_len + self.size);
+        penalty + history.finder_patterns() * 40
+    }
+}
+
+/// Lengths of the last seven runs of a line, the most recent first, where the light border
+/// counts as part of the first run
+struct RunHistory {
+    size: usize,
+    runs: [usize; 7],
+}
+
+impl RunHistory {
+    fn new(size: usize) -> Self {
+        RunHistory { size, runs: [0; 7] }
+    }
+
+    fn push(&mut self, mut run_len: usize) {
+        if self.runs[0] == 0 {
+            run_len += self.size;
+        }
+        self.runs.copy_within(0..6, 1);
+        self.runs[0] = run_len;
+    }
+
+    /// Number of dark-light-dark-dark-dark-light-dark patterns with four light modules on
+    /// either side ending in the latest runs
+    fn finder_patterns(&self) -> usize {
+        let r = &self.runs;
+        let n = r[1];
+        let core = n > 0 && r[2] == n && r[3] == n * 3 && r[4] == n && r[5] == n;
+        usize::from(core && r[0] >= n * 4 && r[6] >= n)
+            + usize::from(core && r[6] >= n * 4 && r[0] >= n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Identification, Version};
+    use qrcode::bits::Bits as ReferenceBits;
+    use qrcode::canvas::{Canvas, MaskPattern};
+    use qrcode::{Color, EcLevel, Version as ReferenceVersion};
+
+    /// The modules of the reference implementation for the same input and mask
+    fn reference(code: &QrCode, payload: &[u8], mask: MaskPattern) -> Vec<bool> {
+        let version = ReferenceVersion::Normal(code.version() as i16);
+        let mut bits = ReferenceBits::new(version);
+        if let Some(eci) = code.eci() {
+            bits.push_eci_designator(eci).unwrap();
+        }
+        bits.push_byte_data(payload).unwrap();
+        bits.push_terminator(EcLevel::M).unwrap();
+        let data = bits.into_bytes();
+        let (data, ec) = qrcode::ec::construct_codewords(&data, version, EcLevel::M).unwrap();
+        let mut canvas = Canvas::new(version, EcLevel::M);
+        canvas.draw_all_functional_patterns();
+        canvas.draw_data(&data, &ec);
+        canvas.apply_mask(mask);
+        canvas
+            .into_colors()
+            .into_iter()
+            .map(|c| c == Color::Dark)
+            .collect()
+    }
+
+    fn matches_reference(code: &QrCode, payload: &[u8]) -> bool {
+        let masks = [
+            MaskPattern::Checkerboard,
+            MaskPattern::HorizontalLines,
+            MaskPattern::VerticalLines,
+            MaskPattern::DiagonalLines,
+            MaskPattern::LargeCheckerboard,
+            MaskPattern::Fields,
+            MaskPattern::Diamonds,
+            MaskPattern::Meadow,
+        ];
+        masks
+            .into_iter()
+            .any(|mask| reference(code, payload, mask) == code.modules)
+    }
+
+    fn epc(character_set: CharacterSet, information: &str) -> Epc {
+        Epc::builder()
+            .version(Version::V1)
+            .character_set(character_set)
+            .identification(Identification::Sct)
+            .bic("GENODEF1SLR")
+            .beneficiary("Codeberg e.V.")
+            .iban("DE90 8306 5408 0004 1042 42")
+            .amount("10.00")
+            .information(information)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn encoding_works() {
+        for information in ["", "Spende", &"x".repeat(70)] {
+            for character_set in [CharacterSet::UTF8, CharacterSet::Iso8859_1] {
+                let epc = epc(character_set, information);
+                let code = epc.to_qr_code().unwrap();
+                assert!(matches_reference(&code, &epc.to_bytes().unwrap()));
+            }
+        }
+    }
+
+    #[test]
+    fn encoding_uses_eci_of_character_set() {
+        let code = epc(CharacterSet::UTF8, "").to_qr_code().unwrap();
+        assert_eq!(code.eci(), Some(26));
+        let code = epc(CharacterSet::Iso8859_1, "").to_qr_code().unwrap();
+        assert_eq!(code.eci(), None);
+        let code = epc(CharacterSet::Iso8859_15, "").to_qr_code().unwrap();
+        assert_eq!(code.eci(), Some(17));
+    }
+
+    #[test]
+    fn encoding_all_versions_works() {
+        for version in 1..=MAX_VERSION {
+            let (data, _) = capacity(version);
+            let payload = vec![b'A'; data - (4 + count_len(version)).div_ceil(8)];
+            let code = QrCode::encode(&payload, None).unwrap();
+            assert_eq!(code.version(), version);
+            assert_eq!(code.size(), version as usize * 4 + 17);
+            assert!(matches_reference(&code, &payload));
+        }
+    }
+
+    #[test]
+    fn largest_payload_fits_into_version_13() {
+        let code = QrCode::encode(&[b'A'; MAX_PAYLOAD_LEN], None).unwrap();
+        assert_eq!(code.version(), MAX_VERSION);
+        assert!(QrCode::encode(&[b'A'; MAX_PAYLOAD_LEN], Some(26)).is_none());
+    }
+}
@@ -0,0 +1,184 @@
+use std::ops::Range;
+
+/// IBAN format of a country as published in the SWIFT IBAN registry
+pub(crate) struct IbanFormat {
+    /// ISO 3166 country code
+    pub(crate) country: &'static str,
+    /// Length of the whole IBAN
+    pub(crate) length: usize,
+    /// BBAN structure in registry notation, e.g. `8!n10!n`
+    pub(crate) bban: &'static str,
+    /// Position of the bank identifier within the BBAN
+    pub(crate) bank_identifier: Range<usize>,
+}
+
+/// Look up the IBAN format of a country
+pub(crate) fn lookup(country: &str) -> Option<&'static IbanFormat> {
+    IBAN_FORMATS
+        .binary_search_by(|f| f.country.cmp(country))
+        .ok()
+        .map(|i| &IBAN_FORMATS[i])
+}
+
+impl IbanFormat {
+    /// Check a BBAN against the structure, where `n` stands for digits, `a` for uppercase
+    /// letters and `c` for both
+    pub(crate) fn matches(&self, bban: &str) -> bool {
+        let mut chars = bban.chars();
+        for (count, kind) in self.parts() {
+            for _ in 0..count {
+                let ok = match (kind, chars.next()) {
+                    ('n', Some(c)) => c.is_ascii_digit(),
+                    ('a', Some(c)) => c.is_ascii_uppercase(),
+                    ('c', Some(c)) => c.is_ascii_digit() || c.is_ascii_uppercase(),
+                    _ => false,
+                };
+                if !ok {
+                    return false;
+                }
+            }
+        }
+        chars.next().is_none()
+    }
+
+    /// The parts of the structure as pairs of length and character kind
+    fn parts(&self) -> impl Iterator<Item = (usize, char)> {
+        self.bban.split_inclusive(['n', 'a', 'c']).map(|part| {
+            let (count, kind) = part.split_at(part.len() - 1);
+            let count = count.trim_end_matches('!').parse().unwrap_or(0);
+            (count, kind.chars().next().unwrap_or('c'))
+        })
+    }
+}
+
+macro_rules! formats {
+    ($($country:literal $length:literal $bban:literal $bank_identifier:expr,)*) => {
+        [$(IbanFormat {
+            country: $country,
+            length: $length,
+            bban: $bban,
+            bank_identifier: $bank_identifier,
+        },)*]
+    };
+}
+
+/// The IBAN formats of all countries in the SWIFT IBAN registry, sorted by country code
+const IBAN_FORMATS: [IbanFormat; 89] = formats![
+    "AD" 24 "4!n4!n12!c" 0..4,
+    "AE" 23 "3!n16!n" 0..3,
+    "AL" 28 "8!n16!c" 0..3,
+    "AT" 20 "5!n11!n" 0..5,
+    "AZ" 28 "4!a20!c" 0..4,
+    "BA" 20 "3!n3!n8!n2!n" 0..3,
+    "BE" 16 "3!n7!n2!n" 0..3,
+    "BG" 22 "4!a4!n2!n8!c" 0..4,
+    "BH" 22 "4!a14!c" 0..4,
+    "BI" 27 "5!n5!n11!n2!n" 0..5,
+    "BR" 29 "8!n5!n10!n1!a1!c" 0..8,
+    "BY" 28 "4!c4!n16!c" 0..4,
+    "CH" 21 "5!n12!c" 0..5,
+    "CR" 22 "4!n14!n" 0..4,
+    "CY" 28 "3!n5!n16!c" 0..3,
+    "CZ" 24 "4!n6!n10!n" 0..4,
+    "DE" 22 "8!n10!n" 0..8,
+    "DJ" 27 "5!n5!n11!n2!n" 0..5,
+    "DK" 18 "4!n9!n1!n" 0..4,
+    "DO" 28 "4!c20!n" 0..4,
+    "EE" 20 "2!n2!n11!n1!n" 0..2,
+    "EG" 29 "4!n4!n17!n" 0..4,
+    "ES" 24 "4!n4!n1!n1!n10!n" 0..4,
+    "FI" 18 "3!n11!n" 0..3,
+    "FK" 18 "2!a12!n" 0..2,
+    "FO" 18 "4!n9!n1!n" 0..4,
+    "FR" 27 "5!n5!n11!c2!n" 0..5,
+    "GB" 22 "4!a6!n8!n" 0..4,
+    "GE" 22 "2!a16!n" 0..2,
+    "GI" 23 "4!a15!c" 0..4,
+    "GL" 18 "4!n9!n1!n" 0..4,
+    "GR" 27 "3!n4!n16!c" 0..3,
+    "GT" 28 "4!c20!c" 0..4,
+    "HN" 28 "4!a20!n" 0..4,
+    "HR" 21 "7!n10!n" 0..7,
+    "HU" 28 "3!n4!n1!n15!n1!n" 0..3,
+    "IE" 22 "4!a6!n8!n" 0..4,
+    "IL" 23 "3!n3!n13!n" 0..3,
+    "IQ" 23 "4!a3!n12!n" 0..4,
+    "IS" 26 "4!n2!n6!n10!n" 0..2,
+    "IT" 27 "1!a5!n5!n12!c" 1..6,
+    "JO" 30 "4!a4!n18!c" 0..4,
+    "KW" 30 "4!a22!c" 0..4,
+    "KZ" 20 "3!n13!c" 0..3,
+    "LB" 28 "4!n20!c" 0..4,
+    "LC" 32 "4!a24!c" 0..4,
+    "LI" 21 "5!n12!c" 0..5,
+    "LT" 20 "5!n11!n" 0..5,
+    "LU" 20 "3!n13!c" 0..3,
+    "LV" 21 "4!a13!c" 0..4,
+    "LY" 25 "3!n3!n15!n" 0..3,
+    "MC" 27 "5!n5!n11!c2!n" 0..5,
+    "MD" 24 "2!c18!c" 0..2,
+    "ME" 22 "3!n13!n2!n" 0..3,
+    "MK" 19 "3!n10!c2!n" 0..3,
+    "MN" 20 "4!n12!n" 0..4,
+    "MR" 27 "5!n5!n11!n2!n" 0..5,
+    "MT" 31 "4!a5!n18!c" 0..4,
+    "MU" 30 "4!a2!n2!n12!n3!n3!a" 0..6,
+    "NI" 28 "4!a20!n" 0..4,
+    "NL" 18 "4!a10!n" 0..4,
+    "NO" 15 "4!n6!n1!n" 0..4,
+    "OM" 23 "3!n16!c" 0..3,
+    "PK" 24 "4!a16!c" 0..4,
+    "PL" 28 "8!n16!n" 0..8,
+    "PS" 29 "4!a21!c" 0..4,
+    "PT" 25 "4!n4!n11!n2!n" 0..4,
+    "QA" 29 "4!a21!c" 0..4,
+    "RO" 24 "4!a16!c" 0..4,
+    "RS" 22 "3!n13!n2!n" 0..3,
+    "RU" 33 "9!n5!n15!c" 0..9,
+    "SA" 24 "2!n18!c" 0..2,
+    "SC" 31 "4!a2!n2!n16!n3!a" 0..6,
+    "SD" 18 "2!n12!n" 0..2,
+    "SE" 24 "3!n16!n1!n" 0..3,
+    "SI" 19 "5!n8!n2!n" 0..5,
+    "SK" 24 "4!n6!n10!n" 0..4,
+    "SM" 27 "1!a5!n5!n12!c" 1..6,
+    "SO" 23 "4!n3!n12!n" 0..4,
+    "ST" 25 "4!n4!n11!n2!n" 0..4,
+    "SV" 28 "4!a20!n" 0..4,
+    "TL" 23 "3!n14!n2!n" 0..3,
+    "TN" 24 "2!n3!n13!n2!n" 0..2,
+    "TR" 26 "5!n1!n16!c" 0..5,
+    "UA" 29 "6!n19!c" 0..6,
+    "VA" 22 "3!n15!n" 0..3,
+    "VG" 24 "4!a16!n" 0..4,
+    "XK" 20 "4!n10!n2!n" 0..2,
+    "YE" 30 "4!a4!n18!c" 0..4,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_is_consistent() {
+        assert!(IBAN_FORMATS.is_sorted_by_key(|f| f.country));
+        for format in IBAN_FORMATS.iter() {
+            let bban_len: usize = format.parts().map(|(count, _)| count).sum();
+            assert_eq!(format.length, bban_len + 4, "{}", format.country);
+            assert!(format.bank_identifier.end <= bban_len, "{}", format.country);
+        }
+    }
+
+    #[test]
+    fn matching_bbans_works() {
+        let de = lookup("DE").unwrap();
+        assert!(de.matches("370400440532013000"));
+        assert!(!de.matches("37040044053201300A"));
+        assert!(!de.matches("37040044053201300"));
+        assert!(!de.matches("3704004405320130000"));
+        let br = lookup("BR").unwrap();
+        assert!(br.matches("00360305000010009795493C1"));
+        assert!(!br.matches("0036030500001000979549311"));
+        assert!(lookup("XX").is_none());
+    }
+}
@@ -0,0 +1,357 @@
+//! SVG rendering of EPC QR codes
+//!
+//! # Examples
+//! ```
+//! use epcgen::svg::SvgOptions;
+//! use epcgen::{CharacterSet, Epc, Identification, Version};
+//!
+//! let epc = Epc::builder()
+//!     .version(Version::V2)
+//!     .character_set(CharacterSet::UTF8)
+//!     .identification(Identification::Sct)
+//!     .beneficiary("Codeberg e.V.")
+//!     .iban("DE90 8306 5408 0004 1042 42")
+//!     .build()
+//!     .unwrap();
+//! let options = SvgOptions {
+//!     size_mm: Some(40.0),
+//!     frame: true,
+//!     ..SvgOptions::default()
+//! };
+//! let svg = epc.render_svg(&options).unwrap();
+//! assert!(svg.contains("Zahlen mit Code"));
+//! ```
+
+use crate::qr::{MIN_MODULE_SIZE_MM, QUIET_ZONE, QrCode};
+use crate::{Epc, EpcError};
+use std::error::Error;
+use std::fmt::{Display, Write};
+
+/// Options for rendering an EPC as SVG
+#[derive(Debug, PartialEq, Clone)]
+pub struct SvgOptions {
+    /// Width of a module in pixels, unless a physical size is given
+    pub module_size: u32,
+    /// Width of the light border around the code in modules, at least [`QUIET_ZONE`] inside a
+    /// frame
+    pub quiet_zone: usize,
+    /// Colour of the dark modules, e.g. `#000000`
+    pub dark: String,
+    /// Colour of the light modules and the background
+    pub light: String,
+    /// Width of the whole image in millimetres, large enough for modules of at least
+    /// [`MIN_MODULE_SIZE_MM`]
+    pub size_mm: Option<f64>,
+    /// Draw a frame with a caption around the code, as recommended by the German and Austrian
+    /// guidelines
+    pub frame: bool,
+    /// Caption on top of the frame
+    pub caption: String,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        SvgOptions {
+            module_size: 4,
+            quiet_zone: QUIET_ZONE,
+            dark: "#000000".to_string(),
+            light: "#ffffff".to_string(),
+            size_mm: None,
+            frame: false,
+            caption: "Zahlen mit Code".to_string(),
+        }
+    }
+}
+
+/// Possible errors when rendering an EPC as SVG
+#[derive(Debug, PartialEq, Clone)]
+pub enum SvgError {
+    /// The EPC can't be encoded
+    Epc(EpcError),
+    /// The module size is zero
+    InvalidModuleSize,
+    /// The size isn't a positive number of millimetres
+    InvalidSize { size_mm: f64 },
+    /// The modules would be smaller than [`MIN_MODULE_SIZE_MM`] at the requested size
+    ModuleTooSmall { actual_mm: f64, min_mm: f64 },
+}
+
+impl Display for SvgError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SvgError::Epc(e) => write!(f, "{}", e),
+            SvgError::InvalidModuleSize => write!(f, "Module size must be at least 1 pixel"),
+            SvgError::InvalidSize { size_mm } => {
+                write!(f, "Invalid size {} mm, must be a positive number", size_mm)
+            }
+            SvgError::ModuleTooSmall { actual_mm, min_mm } => write!(
+                f,
+                "Modules of {:.3} mm are too small to scan (min {} mm)",
+                actual_mm, min_mm
+            ),
+        }
+    }
+}
+
+impl Error for SvgError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SvgError::Epc(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<EpcError> for SvgError {
+    fn from(e: EpcError) -> Self {
+        SvgError::Epc(e)
+    }
+}
+
+impl Epc {
+    /// Render the QR code of the EPC as SVG document
+    pub fn render_svg(&self, options: &SvgOptions) -> Result<String, SvgError> {
+        render(&self.to_qr_code()?, options)
+    }
+}
+
+/// Render a QR code as SVG document
+pub fn render(code: &QrCode, options: &SvgOptions) -> Result<String, SvgError> {
+    if options.module_size == 0 {
+        return Err(SvgError::InvalidModuleSize);
+    }
+    let size = code.size() as f64;
+    // The dark frame needs the full quiet zone to keep the finder patterns recognizable
+    let quiet_zone = if options.frame {
+        options.quiet_zone.max(QUIET_ZONE)
+    } else {
+        options.quiet_zone
+    } as f64;
+    let framed = size + 2.0 * quiet_zone;
+    // The caption is centred on the top line of the frame and needs half its height above
+    let font_size = framed * 0.6 / 9.0;
+    let margin = if options.frame { font_size / 2.0 } else { 0.0 };
+    let frame = if options.frame { 1.0 } else { 0.0 };
+    let offset = margin + frame + quiet_zone;
+    let width = framed + 2.0 * (frame + margin);
+
+    let (svg_width, svg_height) = match options.size_mm {
+        Some(mm) if !(mm.is_finite() && mm > 0.0) => {
+            return Err(SvgError::InvalidSize { size_mm: mm });
+        }
+        Some(mm) if mm / width < MIN_MODULE_SIZE_MM => {
+            return Err(SvgError::ModuleTooSmall {
+                actual_mm: mm / width,
+                min_mm: MIN_MODULE_SIZE_MM,
+            });
+        }
+        Some(mm) => (format!("{}mm", mm), format!("{}mm", mm)),
+        None => {
+            let px = width * options.module_size as f64;
+            (format!("{}", px), format!("{}", px))
+        }
+    };
+
+    let mut svg = String::new();
+    let _ = write!(
+        svg,
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<svg xmlns="http://www.w3.org/2000/svg" version="1.1" width="{}" height="{}" viewBox="0 0 {} {}" shape-rendering="crispEdges">
+<rect width="{}" height="{}" fill="{}"/>
+"#,
+        svg_width,
+        svg_height,
+        width,
+        width,
+        width,
+        width,
+        escape(&options.light)
+    );
+
+    if options.frame {
+        let inset = margin + frame / 2.0;
+        let _ = writeln!(
+            svg,
+            r#"<rect x="{}" y="{}" width="{}" height="{}" rx="{}" fill="none" stroke="{}" stroke-width="{}" shape-rendering="geometricPrecision"/>"#,
+            inset,
+            inset,
+            framed + frame,
+            framed + frame,
+            quiet_zone,
+            escape(&options.dark),
+            frame
+        );
+        let caption_width = framed * 0.6;
+        let _ = writeln!(
+            svg,
+            r#"<rect x="{}" y="0" width="{}" height="{}" fill="{}"/>"#,
+            (width - caption_width) / 2.0 - 1.0,
+            caption_width + 2.0,
+            font_size + frame,
+            escape(&options.light)
+        );
+        let _ = writeln!(
+            svg,
+            r#"<text x="{}" y="{}" font-family="sans-serif" font-weight="bold" font-size="{}" text-anchor="middle" dominant-baseline="central" textLength="{}" lengthAdjust="spacingAndGlyphs" fill="{}">{}</text>"#,
+            width / 2.0,
+            inset,
+            font_size,
+            caption_width,
+            escape(&options.dark),
+            escape(&options.caption)
+        );
+    }
+
+    let _ = write!(svg, r#"<path fill="{}" d=""#, escape(&options.dark));
+    for y in 0..code.size() {
+        for x in 0..code.size() {
+            if code.is_dark(x, y) {
+                let _ = write!(svg, "M{} {}h1v1h-1z", x as f64 + offset, y as f64 + offset);
+            }
+        }
+    }
+    svg.push_str("\"/>\n</svg>\n");
+    Ok(svg)
+}
+
+/// Escape the characters with a special meaning in XML
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_epc;
+
+    #[test]
+    fn rendering_works() {
+        let epc = test_epc();
+        let code = epc.to_qr_code().unwrap();
+        let svg = epc.render_svg(&SvgOptions::default()).unwrap();
+        let width = code.size() + 2 * QUIET_ZONE;
+        assert!(svg.starts_with("<?xml"));
+        assert!(svg.contains(&format!(r#"viewBox="0 0 {} {}""#, width, width)));
+        assert!(svg.contains(&format!(r#"width="{}""#, width * 4)));
+        assert!(svg.contains("M4 4h1v1h-1z"));
+        let dark = (0..code.size())
+            .flat_map(|y| (0..code.size()).map(move |x| (x, y)))
+            .filter(|(x, y)| code.is_dark(*x, *y))
+            .count();
+        assert_eq!(svg.matches("h1v1h-1z").count(), dark);
+        assert!(!svg.contains("<text"));
+    }
+
+    #[test]
+    fn rendering_with_options_works() {
+        let options = SvgOptions {
+            quiet_zone: 0,
+            dark: "#1a1a1a".to_string(),
+            size_mm: Some(30.0),
+            ..SvgOptions::default()
+        };
+        let svg = test_epc().render_svg(&options).unwrap();
+        assert!(svg.contains(r#"width="30mm" height="30mm""#));
+        assert!(svg.contains(r##"<path fill="#1a1a1a" d="M0 0h1v1h-1z"##));
+    }
+
+    #[test]
+    fn frame_keeps_quiet_zone() {
+        let code = test_epc().to_qr_code().unwrap();
+        let options = SvgOptions {
+            quiet_zone: 0,
+            frame: true,
+            ..SvgOptions::default()
+        };
+        let svg = render(&code, &options).unwrap();
+        let framed = code.size() + 2 * QUIET_ZONE;
+        // The frame is inset by half the caption height and drawn with a width of one module
+        let font_size = framed as f64 * 0.6 / 9.0;
+        let offset = font_size / 2.0 + 1.0 + QUIET_ZONE as f64;
+        assert!(svg.contains(&format!(
+            r#"width="{}" height="{}" rx"#,
+            framed + 1,
+            framed + 1
+        )));
+        assert!(svg.contains(&format!(r#"d="M{} {}h1v1h-1z"#, offset, offset)));
+    }
+
+    #[test]
+    fn invalid_options_should_fail() {
+        let options = SvgOptions {
+            module_size: 0,
+            ..SvgOptions::default()
+        };
+        assert_eq!(
+            test_epc().render_svg(&options),
+            Err(SvgError::InvalidModuleSize)
+        );
+
+        for size_mm in [0.0, -5.0, f64::INFINITY] {
+            let options = SvgOptions {
+                size_mm: Some(size_mm),
+                ..SvgOptions::default()
+            };
+            assert_eq!(
+                test_epc().render_svg(&options),
+                Err(SvgError::InvalidSize { size_mm })
+            );
+        }
+        let options = SvgOptions {
+            size_mm: Some(f64::NAN),
+            ..SvgOptions::default()
+        };
+        assert!(matches!(
+            test_epc().render_svg(&options),
+            Err(SvgError::InvalidSize { .. })
+        ));
+
+        // 3 mm are 0.07 mm for each of the 41 modules
+        let options = SvgOptions {
+            size_mm: Some(3.0),
+            ..SvgOptions::default()
+        };
+        assert!(matches!(
+            test_epc().render_svg(&options),
+            Err(SvgError::ModuleTooSmall { .. })
+        ));
+        // The frame takes the room of about five modules
+        let options = SvgOptions {
+            size_mm: Some(17.5),
+            ..SvgOptions::default()
+        };
+        assert!(test_epc().render_svg(&options).is_ok());
+        let options = SvgOptions {
+            size_mm: Some(17.5),
+            frame: true,
+            ..SvgOptions::default()
+        };
+        assert!(matches!(
+            test_epc().render_svg(&options),
+            Err(SvgError::ModuleTooSmall { .. })
+        ));
+    }
+
+    #[test]
+    fn rendering_frame_works() {
+        let options = SvgOptions {
+            frame: true,
+            caption: "Pay & go".to_string(),
+            ..SvgOptions::default()
+        };
+        let svg = test_epc().render_svg(&options).unwrap();
+        assert!(svg.contains(">Pay &amp; go</text>"));
+        assert!(svg.contains(r##"stroke="#000000""##));
+    }
+}
@@ -0,0 +1,172 @@
+//! Terminal rendering of EPC QR codes with Unicode half blocks
+//!
+//! Each character shows two modules on top of each other, so the code keeps its square shape
+//! in common terminal fonts.
+//!
+//! # Examples
+//! ```
+//! use epcgen::terminal::TerminalOptions;
+//! use epcgen::{CharacterSet, Epc, Identification, Version};
+//!
+//! let epc = Epc::builder()
+//!     .version(Version::V2)
+//!     .character_set(CharacterSet::UTF8)
+//!     .identification(Identification::Sct)
+//!     .beneficiary("Codeberg e.V.")
+//!     .iban("DE90 8306 5408 0004 1042 42")
+//!     .build()
+//!     .unwrap();
+//! print!("{}", epc.render_terminal(&TerminalOptions::default()).unwrap());
+//! ```
+
+use crate::qr::{QUIET_ZONE, QrCode};
+use crate::{Epc, EpcError};
+
+/// How the modules are drawn
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum TerminalStyle {
+    /// Plain half block characters in the colours of the terminal
+    #[default]
+    HalfBlocks,
+    /// Half block characters with explicit black and white ANSI colours, independent of the
+    /// colours of the terminal
+    Ansi,
+}
+
+/// Options for rendering an EPC in a terminal
+#[derive(Debug, PartialEq, Clone)]
+pub struct TerminalOptions {
+    /// How the modules are drawn
+    pub style: TerminalStyle,
+    /// Swap dark and light modules
+    ///
+    /// With [`TerminalStyle::HalfBlocks`] the dark modules are drawn with the text colour,
+    /// which fits terminals with a light background. Inverting fits a dark background.
+    pub inverted: bool,
+    /// Width of the light border around the code in modules
+    pub quiet_zone: usize,
+}
+
+impl Default for TerminalOptions {
+    fn default() -> Self {
+        TerminalOptions {
+            style: TerminalStyle::default(),
+            inverted: false,
+            quiet_zone: QUIET_ZONE,
+        }
+    }
+}
+
+impl Epc {
+    /// Render the QR code of the EPC for printing to a terminal
+    pub fn render_terminal(&self, options: &TerminalOptions) -> Result<String, EpcError> {
+        Ok(render(&self.to_qr_code()?, options))
+    }
+}
+
+/// Render a QR code for printing to a terminal, one line per two rows of modules
+pub fn render(code: &QrCode, options: &TerminalOptions) -> String {
+    let width = code.size() + 2 * options.quiet_zone;
+    // Whether a module is drawn, i.e. it is dark or, when inverted, light. Modules outside of
+    // the code belong to the quiet zone and are light.
+    let drawn = |x: usize, y: usize| match (
+        x.checked_sub(options.quiet_zone),
+        y.checked_sub(options.quiet_zone),
+    ) {
+        (Some(x), Some(y)) => code.is_dark(x, y) != options.inverted,
+        _ => options.inverted,
+    };
+
+    let mut output = String::new();
+    for y in (0..width).step_by(2) {
+        for x in 0..width {
+            let top = drawn(x, y);
+            // The lower half of the last line is outside of the quiet zone for odd widths
+            let bottom = if y + 1 < width {
+                drawn(x, y + 1)
+            } else {
+                options.inverted
+            };
+            match options.style {
+                TerminalStyle::HalfBlocks => output.push(match (top, bottom) {
+                    (true, true) => '█',
+                    (true, false) => '▀',
+                    (false, true) => '▄',
+                    (false, false) => ' ',
+                }),
+                TerminalStyle::Ansi => {
+                    let foreground = if top { 30 } else { 97 };
+                    let background = if bottom { 40 } else { 107 };
+                    output.push_str(&format!("\x1b[{};{}m▀", foreground, background));
+                }
+            }
+        }
+        if options.style == TerminalStyle::Ansi {
+            output.push_str("\x1b[0m");
+        }
+        output.push('\n');
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CharacterSet, Identification, Version};
+
+    fn code() -> QrCode {
+        Epc::builder()
+            .version(Version::V2)
+            .character_set(CharacterSet::UTF8)
+            .identification(Identification::Sct)
+            .beneficiary("Codeberg e.V.")
+            .iban("DE90 8306 5408 0004 1042 42")
+            .build()
+            .unwrap()
+            .to_qr_code()
+            .unwrap()
+    }
+
+    #[test]
+    fn rendering_half_blocks_works() {
+        let code = code();
+        let output = render(&code, &TerminalOptions::default());
+        let lines: Vec<&str> = output.lines().collect();
+        let width = code.size() + 2 * QUIET_ZONE;
+        assert_eq!(lines.len(), width.div_ceil(2));
+        assert!(lines.iter().all(|l| l.chars().count() == width));
+        assert!(lines[0].chars().all(|c| c == ' '));
+        // The finder pattern starts in the third line, with a light ring in its second row
+        assert_eq!(lines[2].chars().nth(4), Some('█'));
+        assert_eq!(lines[2].chars().nth(5), Some('▀'));
+    }
+
+    #[test]
+    fn rendering_inverted_works() {
+        let code = code();
+        let options = TerminalOptions {
+            inverted: true,
+            quiet_zone: 1,
+            ..TerminalOptions::default()
+        };
+        let output = render(&code, &options);
+        let lines: Vec<&str> = output.lines().collect();
+        assert!(lines[0].starts_with("█▀▀▀"));
+        // An odd width leaves the lower half of the last line light
+        assert!(lines.last().unwrap().chars().all(|c| c == '█'));
+    }
+
+    #[test]
+    fn rendering_ansi_works() {
+        let code = code();
+        let options = TerminalOptions {
+            style: TerminalStyle::Ansi,
+            ..TerminalOptions::default()
+        };
+        let output = render(&code, &options);
+        let first = output.lines().next().unwrap();
+        assert!(first.starts_with("\x1b[97;107m▀"));
+        assert!(first.ends_with("\x1b[0m"));
+        assert_eq!(first.matches('▀').count(), code.size() + 2 * QUIET_ZONE);
+    }
+}
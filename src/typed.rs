@@ -0,0 +1,258 @@
+//! Builder checking at compile time that all mandatory fields are set
+//!
+//! # Examples
+//! ```
+//! use epcgen::{CharacterSet, Epc, Identification};
+//!
+//! let epc = Epc::typed_builder()
+//!     .v1("GENODEF1SLR")
+//!     .character_set(CharacterSet::UTF8)
+//!     .identification(Identification::Sct)
+//!     .beneficiary("Codeberg e.V.")
+//!     .iban("DE90 8306 5408 0004 1042 42")
+//!     .amount("10.00")
+//!     .build();
+//! assert!(epc.is_ok());
+//! ```
+//!
+//! Forgetting a mandatory field doesn't compile:
+//! ```compile_fail
+//! use epcgen::{CharacterSet, Epc, Identification};
+//!
+//! let epc = Epc::typed_builder()
+//!     .v2()
+//!     .character_set(CharacterSet::UTF8)
+//!     .identification(Identification::Sct)
+//!     .iban("DE90 8306 5408 0004 1042 42")
+//!     .build();
+//! ```
+
+use crate::{
+    Amount, BeneficiaryName, Bic, Builder, CharacterSet, ControlCharacters, Epc, EpcError, Iban,
+    Identification, Purpose, PurposeCodes, Remittance, RemittanceText, RfReference, Version,
+};
+use std::borrow::Cow;
+use std::marker::PhantomData;
+
+/// State of a mandatory field that is not set yet
+#[derive(Debug, Clone, Copy)]
+pub struct Missing;
+
+/// State of a mandatory field that is set
+#[derive(Debug, Clone, Copy)]
+pub struct Set;
+
+/// Builder for EPCs, whose `build` method is only available once the version, character set,
+/// identification, beneficiary and IBAN are set
+///
+/// The type parameters track the state ([`Missing`] or [`Set`]) of these fields. As
+/// [`Version::V1`] requires a BIC, it can only be chosen together with one by
+/// [`TypedBuilder::v1`]. The values are still validated by [`TypedBuilder::build`].
+#[derive(Debug, Clone)]
+pub struct TypedBuilder<'a, V, C, I, N, A> {
+    builder: Builder<'a>,
+    state: PhantomData<(V, C, I, N, A)>,
+}
+
+impl<'a> TypedBuilder<'a, Missing, Missing, Missing, Missing, Missing> {
+    pub fn new() -> Self {
+        Self {
+            builder: Builder::new(),
+            state: PhantomData,
+        }
+    }
+}
+
+impl<'a> Default for TypedBuilder<'a, Missing, Missing, Missing, Missing, Missing> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, V, C, I, N, A> TypedBuilder<'a, V, C, I, N, A> {
+    fn with<V2, C2, I2, N2, A2>(
+        self,
+        f: impl FnOnce(Builder<'a>) -> Builder<'a>,
+    ) -> TypedBuilder<'a, V2, C2, I2, N2, A2> {
+        TypedBuilder {
+            builder: f(self.builder),
+            state: PhantomData,
+        }
+    }
+
+    /// Set the BIC (Business Identifier Code) of the recipient bank, which is optional in
+    /// [`Version::V2`]
+    pub fn bic<B: TryInto<Bic>>(self, bic: B) -> Self
+    where
+        B::Error: Into<EpcError>,
+    {
+        self.with(|b| b.bic(bic))
+    }
+
+    /// Set the amount of the transfer, either as an [`Amount`] or as a string like `"10.50"`
+    pub fn amount<T: TryInto<Amount>>(self, amount: T) -> Self {
+        self.with(|b| b.amount(amount))
+    }
+
+    /// Set the purpose code
+    pub fn purpose(self, purpose: Purpose) -> Self {
+        self.with(|b| b.purpose(purpose))
+    }
+
+    /// Set the Remittance, either as a [`Remittance`], an [`RfReference`] or a
+    /// [`RemittanceText`]
+    pub fn remittance(self, remittance: impl Into<Remittance>) -> Self {
+        self.with(|b| b.remittance(remittance))
+    }
+
+    /// Set a structured RF creditor reference, which isn't checked again when building
+    pub fn rf_reference(self, reference: RfReference) -> Self {
+        self.with(|b| b.rf_reference(reference))
+    }
+
+    /// Set an unstructured remittance text, which isn't checked again when building
+    pub fn remittance_text(self, text: RemittanceText) -> Self {
+        self.with(|b| b.remittance_text(text))
+    }
+
+    /// Set the Beneficiary to Originator information
+    pub fn information(self, information: impl Into<Cow<'a, str>>) -> Self {
+        self.with(|b| b.information(information))
+    }
+
+    /// Set how control characters like line breaks in free-text fields are treated
+    pub fn control_characters(self, control_characters: ControlCharacters) -> Self {
+        self.with(|b| b.control_characters(control_characters))
+    }
+
+    /// Set which purpose codes are accepted
+    pub fn purpose_codes(self, purpose_codes: PurposeCodes) -> Self {
+        self.with(|b| b.purpose_codes(purpose_codes))
+    }
+}
+
+impl<'a, C, I, N, A> TypedBuilder<'a, Missing, C, I, N, A> {
+    /// Set [`Version::V1`] together with the BIC it requires
+    pub fn v1<B: TryInto<Bic>>(self, bic: B) -> TypedBuilder<'a, Set, C, I, N, A>
+    where
+        B::Error: Into<EpcError>,
+    {
+        self.with(|b| b.version(Version::V1).bic(bic))
+    }
+
+    /// Set [`Version::V2`]
+    pub fn v2(self) -> TypedBuilder<'a, Set, C, I, N, A> {
+        self.with(|b| b.version(Version::V2))
+    }
+}
+
+impl<'a, V, I, N, A> TypedBuilder<'a, V, Missing, I, N, A> {
+    /// Set the CharacterSet
+    pub fn character_set(self, character_set: CharacterSet) -> TypedBuilder<'a, V, Set, I, N, A> {
+        self.with(|b| b.character_set(character_set))
+    }
+}
+
+impl<'a, V, C, N, A> TypedBuilder<'a, V, C, Missing, N, A> {
+    /// Set the Identification
+    pub fn identification(
+        self,
+        identification: Identification,
+    ) -> TypedBuilder<'a, V, C, Set, N, A> {
+        self.with(|b| b.identification(identification))
+    }
+}
+
+impl<'a, V, C, I, A> TypedBuilder<'a, V, C, I, Missing, A> {
+    /// Set the name of the beneficiary
+    pub fn beneficiary(
+        self,
+        beneficiary: impl Into<Cow<'a, str>>,
+    ) -> TypedBuilder<'a, V, C, I, Set, A> {
+        self.with(|b| b.beneficiary(beneficiary))
+    }
+
+    /// Set the name of the beneficiary, which isn't checked again when building
+    pub fn beneficiary_name(
+        self,
+        beneficiary: BeneficiaryName,
+    ) -> TypedBuilder<'a, V, C, I, Set, A> {
+        self.with(|b| b.beneficiary_name(beneficiary))
+    }
+}
+
+impl<'a, V, C, I, N> TypedBuilder<'a, V, C, I, N, Missing> {
+    /// Set the IBAN of the beneficiary
+    pub fn iban<T: TryInto<Iban>>(self, iban: T) -> TypedBuilder<'a, V, C, I, N, Set> {
+        self.with(|b| b.iban(iban))
+    }
+}
+
+impl<'a> TypedBuilder<'a, Set, Set, Set, Set, Set> {
+    /// Build the resulting EPC, validating the values like [`Builder::build`]
+    pub fn build(&self) -> Result<Epc, EpcError> {
+        self.builder.build()
+    }
+
+    /// Turn into the dynamic [`Builder`]
+    pub fn into_builder(self) -> Builder<'a> {
+        self.builder
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn typed_builder_works() {
+        let epc = TypedBuilder::new()
+            .iban("DE90 8306 5408 0004 1042 42")
+            .beneficiary("Codeberg e.V.")
+            .identification(Identification::Sct)
+            .character_set(CharacterSet::UTF8)
+            .v2()
+            .remittance(Remittance::Text("for the good cause".to_string()))
+            .build();
+        let expected = Epc::builder()
+            .version(Version::V2)
+            .character_set(CharacterSet::UTF8)
+            .identification(Identification::Sct)
+            .beneficiary("Codeberg e.V.")
+            .iban("DE90 8306 5408 0004 1042 42")
+            .remittance(Remittance::Text("for the good cause".to_string()))
+            .build();
+        assert!(epc.is_ok());
+        assert_eq!(epc, expected);
+    }
+
+    #[test]
+    fn typed_builder_sets_the_bic_of_version1() {
+        let epc = Epc::typed_builder()
+            .v1("GENODEF1SLR")
+            .character_set(CharacterSet::UTF8)
+            .identification(Identification::Sct)
+            .beneficiary("Codeberg e.V.")
+            .iban("DE90 8306 5408 0004 1042 42")
+            .build()
+            .unwrap();
+        assert_eq!(epc.version(), Version::V1);
+        assert_eq!(epc.bic(), Some("GENODEF1SLR"));
+    }
+
+    #[test]
+    fn typed_builder_still_validates_values() {
+        let builder = Epc::typed_builder()
+            .v1("HELLO")
+            .character_set(CharacterSet::UTF8)
+            .identification(Identification::Sct)
+            .beneficiary("Codeberg e.V.")
+            .iban("DE90 8306 5408 0004 1042 42");
+        assert_eq!(
+            builder.build(),
+            Err(EpcError::InvalidBICLength { actual: 5 })
+        );
+        let builder = builder.bic("GENODEF1SLR").amount("0.00");
+        assert_eq!(builder.build(), Err(EpcError::InvalidAmount));
+    }
+}
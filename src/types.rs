@@ -0,0 +1,284 @@
+//! Validated values of EPC fields
+//!
+//! Each type can only hold a value satisfying the rules of its field, so it can be passed to
+//! [`Builder`](crate::Builder) without being checked again: [`Iban`] and [`Bic`] through
+//! [`Builder::iban`](crate::Builder::iban) and [`Builder::bic`](crate::Builder::bic), the text
+//! values through [`Builder::beneficiary_name`](crate::Builder::beneficiary_name),
+//! [`Builder::rf_reference`](crate::Builder::rf_reference) and
+//! [`Builder::remittance_text`](crate::Builder::remittance_text).
+
+use crate::bic;
+use crate::epcgen::{
+    MAX_BENEFICIARY_LEN, MAX_REMITTANCE_TEXT_LEN, check_len, is_control_character,
+};
+use crate::ibanrf::iban;
+use crate::ibanrf::rf;
+use crate::{EpcError, Field, Remittance};
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Implement the conversions shared by all validated values, based on a `new` function
+macro_rules! validated {
+    ($name:ident) => {
+        impl FromStr for $name {
+            type Err = EpcError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                $name::new(s)
+            }
+        }
+
+        impl TryFrom<&str> for $name {
+            type Error = EpcError;
+
+            fn try_from(value: &str) -> Result<Self, Self::Error> {
+                $name::new(value)
+            }
+        }
+
+        impl TryFrom<String> for $name {
+            type Error = EpcError;
+
+            fn try_from(value: String) -> Result<Self, Self::Error> {
+                $name::new(&value)
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(value: $name) -> Self {
+                value.0.to_string()
+            }
+        }
+    };
+}
+
+/// An IBAN in electronic format, which passed [`iban::is_valid`]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "String", into = "String")
+)]
+pub struct Iban(Arc<str>);
+
+impl Iban {
+    /// Validate an IBAN given in electronic or print format
+    fn new(value: &str) -> Result<Self, EpcError> {
+        let electronic = value.replace(' ', "");
+        if iban::is_valid(&electronic) {
+            Result::Ok(Iban(electronic.into()))
+        } else {
+            Result::Err(EpcError::InvalidIBAN)
+        }
+    }
+
+    /// The country code of the IBAN
+    pub fn country(&self) -> &str {
+        &self.0[0..2]
+    }
+}
+
+validated!(Iban);
+
+/// A BIC, which passed [`bic::is_valid`]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "String", into = "String")
+)]
+pub struct Bic(Arc<str>);
+
+impl Bic {
+    fn new(value: &str) -> Result<Self, EpcError> {
+        if value.chars().any(is_control_character) {
+            return Result::Err(EpcError::ControlCharacter { field: Field::Bic });
+        }
+        let actual = value.chars().count();
+        if actual != 8 && actual != 11 {
+            return Result::Err(EpcError::InvalidBICLength { actual });
+        }
+        if !bic::is_valid(value) {
+            return Result::Err(EpcError::InvalidBIC);
+        }
+        Result::Ok(Bic(value.into()))
+    }
+
+    /// The country code of the BIC
+    pub fn country(&self) -> &str {
+        &self.0[4..6]
+    }
+}
+
+validated!(Bic);
+
+/// A structured RF creditor reference in electronic format, which passed [`rf::is_valid`]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "String", into = "String")
+)]
+pub struct RfReference(Arc<str>);
+
+impl RfReference {
+    /// Validate a reference given in electronic or print format
+    fn new(value: &str) -> Result<Self, EpcError> {
+        let electronic = value.replace(' ', "");
+        if rf::is_valid(&electronic) {
+            Result::Ok(RfReference(electronic.into()))
+        } else {
+            Result::Err(EpcError::InvalidRemittanceReference)
+        }
+    }
+}
+
+validated!(RfReference);
+
+impl From<RfReference> for Remittance {
+    fn from(reference: RfReference) -> Self {
+        Remittance::Reference(reference.0.to_string())
+    }
+}
+
+/// A non-empty name of the Beneficiary without control characters and within the length limit
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "String", into = "String")
+)]
+pub struct BeneficiaryName(Arc<str>);
+
+impl BeneficiaryName {
+    fn new(value: &str) -> Result<Self, EpcError> {
+        if value.is_empty() {
+            return Result::Err(EpcError::MissingBeneficiary);
+        }
+        if value.chars().any(is_control_character) {
+            return Result::Err(EpcError::ControlCharacter {
+                field: Field::Beneficiary,
+            });
+        }
+        check_len(Field::Beneficiary, value, MAX_BENEFICIARY_LEN)?;
+        Result::Ok(BeneficiaryName(value.into()))
+    }
+}
+
+validated!(BeneficiaryName);
+
+/// An unstructured remittance text without control characters and within the length limit
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "String", into = "String")
+)]
+pub struct RemittanceText(Arc<str>);
+
+impl RemittanceText {
+    fn new(value: &str) -> Result<Self, EpcError> {
+        if value.chars().any(is_control_character) {
+            return Result::Err(EpcError::ControlCharacter {
+                field: Field::Remittance,
+            });
+        }
+        check_len(Field::Remittance, value, MAX_REMITTANCE_TEXT_LEN)?;
+        Result::Ok(RemittanceText(value.into()))
+    }
+}
+
+validated!(RemittanceText);
+
+impl From<RemittanceText> for Remittance {
+    fn from(text: RemittanceText) -> Self {
+        Remittance::Text(text.0.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn iban_works() {
+        let iban: Iban = "DE90 8306 5408 0004 1042 42".parse().unwrap();
+        assert_eq!(iban.as_ref(), "DE90830654080004104242");
+        assert_eq!(iban.to_string(), "DE90830654080004104242");
+        assert_eq!(iban.country(), "DE");
+        assert_eq!(
+            Iban::try_from("DE90 8306 5408 0004 1042 43"),
+            Err(EpcError::InvalidIBAN)
+        );
+    }
+
+    #[test]
+    fn bic_works() {
+        let bic = Bic::try_from("GENODEF1SLR").unwrap();
+        assert_eq!(bic.as_ref(), "GENODEF1SLR");
+        assert_eq!(bic.country(), "DE");
+        assert_eq!(
+            Bic::try_from("GENODEF1SL"),
+            Err(EpcError::InvalidBICLength { actual: 10 })
+        );
+        assert_eq!(Bic::try_from("HELLODEF"), Err(EpcError::InvalidBIC));
+    }
+
+    #[test]
+    fn rf_reference_works() {
+        let reference: RfReference = "RF45 G72U UR".parse().unwrap();
+        assert_eq!(reference.as_ref(), "RF45G72UUR");
+        assert_eq!(
+            Remittance::from(reference),
+            Remittance::Reference("RF45G72UUR".to_string())
+        );
+        assert_eq!(
+            RfReference::try_from("RF46G72UUR"),
+            Err(EpcError::InvalidRemittanceReference)
+        );
+    }
+
+    #[test]
+    fn text_values_should_fail() {
+        assert_eq!(
+            BeneficiaryName::try_from(""),
+            Err(EpcError::MissingBeneficiary)
+        );
+        assert_eq!(
+            BeneficiaryName::try_from("Codeberg\ne.V."),
+            Err(EpcError::ControlCharacter {
+                field: Field::Beneficiary
+            })
+        );
+        assert_eq!(
+            BeneficiaryName::try_from("a".repeat(71)),
+            Err(EpcError::FieldTooLong {
+                field: Field::Beneficiary,
+                max: 70,
+                actual: 71
+            })
+        );
+        assert_eq!(
+            RemittanceText::try_from("a".repeat(141)),
+            Err(EpcError::FieldTooLong {
+                field: Field::Remittance,
+                max: 140,
+                actual: 141
+            })
+        );
+        assert!(RemittanceText::try_from("for the good cause").is_ok());
+    }
+}